@@ -0,0 +1,86 @@
+// Copyright 2019 Chainpool
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! SCALE encoding of 100k hashes and uints, per element through `Vec<T>`
+//! against the bulk `SliceCodec` and the zero-copy `SliceView`.
+
+#![feature(test)]
+
+extern crate test;
+
+use impl_codec::scale_codec::{Decode, Encode};
+use primitive_types::{SliceCodec, SliceView, H256, U256};
+use test::{black_box, Bencher};
+
+const LEN: u64 = 100_000;
+
+fn hashes() -> Vec<H256> {
+    (0..LEN).map(|i| H256::from_low_u64_be(i * 7919)).collect()
+}
+
+fn uints() -> Vec<U256> {
+    (0..LEN).map(|i| U256::max_value() / (i + 1)).collect()
+}
+
+#[bench]
+fn h256_vec_encode(b: &mut Bencher) {
+    let values = hashes();
+    b.iter(|| black_box(&values).encode());
+}
+
+#[bench]
+fn h256_encode_slice(b: &mut Bencher) {
+    let values = hashes();
+    b.iter(|| H256::encode_slice(black_box(&values)));
+}
+
+#[bench]
+fn h256_vec_decode(b: &mut Bencher) {
+    let encoded = hashes().encode();
+    b.iter(|| Vec::<H256>::decode(&mut black_box(&encoded[..])).unwrap());
+}
+
+#[bench]
+fn h256_decode_vec(b: &mut Bencher) {
+    let encoded = hashes().encode();
+    b.iter(|| H256::decode_vec(&mut black_box(&encoded[..])).unwrap());
+}
+
+#[bench]
+fn h256_view_slice(b: &mut Bencher) {
+    let encoded = hashes().encode();
+    b.iter(|| {
+        H256::view_slice(&mut black_box(&encoded[..]))
+            .unwrap()
+            .len()
+    });
+}
+
+#[bench]
+fn u256_vec_encode(b: &mut Bencher) {
+    let values = uints();
+    b.iter(|| black_box(&values).encode());
+}
+
+#[bench]
+fn u256_encode_slice(b: &mut Bencher) {
+    let values = uints();
+    b.iter(|| U256::encode_slice(black_box(&values)));
+}
+
+#[bench]
+fn u256_vec_decode(b: &mut Bencher) {
+    let encoded = uints().encode();
+    b.iter(|| Vec::<U256>::decode(&mut black_box(&encoded[..])).unwrap());
+}
+
+#[bench]
+fn u256_decode_vec(b: &mut Bencher) {
+    let encoded = uints().encode();
+    b.iter(|| U256::decode_vec(&mut black_box(&encoded[..])).unwrap());
+}
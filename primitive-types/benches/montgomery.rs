@@ -0,0 +1,74 @@
+// Copyright 2019 Chainpool
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Montgomery multiplication against plain `mul_mod`.
+
+#![feature(test)]
+
+extern crate test;
+
+use primitive_types::{MontgomeryContext, U256, U512};
+use test::{black_box, Bencher};
+
+/// The secp256k1 field prime.
+fn secp256k1_p() -> U256 {
+    U256::from_dec_str(
+        "115792089237316195423570985008687907853269984665640564039457584007908834671663",
+    )
+    .unwrap()
+}
+
+fn operands() -> (U256, U256) {
+    let p = secp256k1_p();
+    (p / 3, p / 7 + 12345)
+}
+
+#[bench]
+fn u256_mul_mod(b: &mut Bencher) {
+    let p = secp256k1_p();
+    let (x, y) = operands();
+    b.iter(|| black_box(x).mul_mod(black_box(y), p));
+}
+
+#[bench]
+fn u256_montgomery_mul(b: &mut Bencher) {
+    let ctx = MontgomeryContext::<U256>::new(secp256k1_p()).unwrap();
+    let (x, y) = operands();
+    let (x, y) = (ctx.to_montgomery(x), ctx.to_montgomery(y));
+    b.iter(|| ctx.mul(black_box(x), black_box(y)));
+}
+
+#[bench]
+fn u256_pow_mod(b: &mut Bencher) {
+    let p = secp256k1_p();
+    let (x, _) = operands();
+    b.iter(|| black_box(x).pow_mod(p - 2, p));
+}
+
+#[bench]
+fn u256_montgomery_pow(b: &mut Bencher) {
+    let p = secp256k1_p();
+    let ctx = MontgomeryContext::<U256>::new(p).unwrap();
+    let x = ctx.to_montgomery(operands().0);
+    b.iter(|| ctx.pow(black_box(x), p - 2));
+}
+
+#[bench]
+fn u512_mul_mod(b: &mut Bencher) {
+    let p = U512::from(secp256k1_p()).pow(2.into()) - 2;
+    let (x, y) = (p / 3, p / 7);
+    b.iter(|| black_box(x).mul_mod(black_box(y), p));
+}
+
+#[bench]
+fn u512_montgomery_mul(b: &mut Bencher) {
+    let p = U512::from(secp256k1_p()).pow(2.into()) - 2;
+    let ctx = MontgomeryContext::<U512>::new(p).unwrap();
+    let (x, y) = (ctx.to_montgomery(p / 3), ctx.to_montgomery(p / 7));
+    b.iter(|| ctx.mul(black_box(x), black_box(y)));
+}
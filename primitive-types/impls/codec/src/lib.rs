@@ -6,29 +6,131 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-//! Parity Codec serialization support for uint and fixed hash.
+//! Parity Codec serialization support for uint, fixed hash and integer arrays.
+//!
+//! Two codec generations are supported, each behind the feature named after its
+//! crate, with the same byte layout:
+//!
+//! - `parity-codec`: the `impl_*_codec!` macros, whose `Decode` returns `Option`.
+//! - `parity-scale-codec`: the `impl_*_scale_codec!` macros, whose `Decode`
+//!   returns `Result<_, Error>`. They also implement `MaxEncodedLen`, and
+//!   `SliceCodec` and `SliceView` for bulk encoding of slices.
+//!
+//! `std` only builds this crate with the standard library. Each backend has
+//! its own `parity-codec-std` and `parity-scale-codec-std` feature, which also
+//! enables the `std` feature of the codec crate, so enabling one backend with
+//! `std` does not pull in the other. The default features are `std` and
+//! `parity-codec-std`, the backend this crate always had.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "parity-codec")]
 #[doc(hidden)]
 pub use parity_codec as codec;
+#[cfg(feature = "parity-scale-codec")]
+#[doc(hidden)]
+pub use parity_scale_codec as scale_codec;
+
+#[cfg(all(feature = "parity-scale-codec", not(feature = "std")))]
+extern crate alloc;
+
+#[cfg(feature = "parity-scale-codec")]
+mod slice;
+
+#[cfg(feature = "parity-scale-codec")]
+#[doc(hidden)]
+pub use slice::{view_slice, LittleEndianPod};
+#[cfg(feature = "parity-scale-codec")]
+pub use slice::{SliceCodec, SliceView};
+
+/// Maximum length of the SCALE encoding of a type, implemented by the scale
+/// codec macros.
+///
+/// `parity-scale-codec` only has this trait from version 2, while this backend
+/// supports the crates on version 1.
+#[cfg(feature = "parity-scale-codec")]
+pub trait MaxEncodedLen: parity_scale_codec::Encode {
+    /// Upper bound of the length of the encoding of any value.
+    fn max_encoded_len() -> usize;
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Primitive integer which the fixed array macros encode as little-endian bytes.
+///
+/// Only the primitive integers implement it, for which every byte pattern is a
+/// valid value.
+pub trait LittleEndianInt: Copy + private::Sealed {
+    /// Size of the encoding in bytes.
+    const SIZE: usize;
+
+    /// Zero value.
+    const ZERO: Self;
+
+    /// Writes the value as little-endian bytes into `bytes` of `SIZE` bytes.
+    fn write_le(self, bytes: &mut [u8]);
+
+    /// Reads a value from little-endian bytes `bytes` of `SIZE` bytes.
+    fn read_le(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_little_endian_int {
+    ($($int:ty),*) => {
+        $(
+            impl private::Sealed for $int {}
+
+            impl LittleEndianInt for $int {
+                const SIZE: usize = core::mem::size_of::<$int>();
+                const ZERO: Self = 0;
+
+                #[inline]
+                fn write_le(self, bytes: &mut [u8]) {
+                    bytes.copy_from_slice(&self.to_le_bytes());
+                }
+
+                #[inline]
+                fn read_le(bytes: &[u8]) -> Self {
+                    let mut array = [0u8; core::mem::size_of::<$int>()];
+                    array.copy_from_slice(bytes);
+                    <$int>::from_le_bytes(array)
+                }
+            }
+        )*
+    };
+}
+
+impl_little_endian_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+// `parity-codec` 3 only implements its traits for some array lengths, so the
+// bytes are read and written directly rather than through `[u8; N]`.
 
 /// Add Parity Codec serialization support to an integer created by `construct_uint!`.
 #[macro_export]
 macro_rules! impl_uint_codec {
     ($name: ident, $len: expr) => {
         impl $crate::codec::Encode for $name {
+            fn encode_to<W: $crate::codec::Output>(&self, dest: &mut W) {
+                let mut bytes = [0u8; $len * 8];
+                self.to_little_endian(&mut bytes);
+                dest.write(&bytes);
+            }
+
             fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
                 let mut bytes = [0u8; $len * 8];
                 self.to_little_endian(&mut bytes);
-                bytes.using_encoded(f)
+                f(&bytes)
             }
         }
 
         impl $crate::codec::Decode for $name {
             fn decode<I: $crate::codec::Input>(input: &mut I) -> Option<Self> {
-                <[u8; $len * 8] as $crate::codec::Decode>::decode(input)
-                    .map(|b| $name::from_little_endian(&b))
+                let mut bytes = [0u8; $len * 8];
+                if input.read(&mut bytes) != $len * 8 {
+                    return None;
+                }
+                Some($name::from_little_endian(&bytes))
             }
         }
     };
@@ -39,52 +141,264 @@ macro_rules! impl_uint_codec {
 macro_rules! impl_fixed_hash_codec {
     ($name: ident, $len: expr) => {
         impl $crate::codec::Encode for $name {
+            fn encode_to<W: $crate::codec::Output>(&self, dest: &mut W) {
+                dest.write(&self.0);
+            }
+
             fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
-                self.0.using_encoded(f)
+                f(&self.0)
+            }
+        }
+
+        impl $crate::codec::Decode for $name {
+            fn decode<I: $crate::codec::Input>(input: &mut I) -> Option<Self> {
+                let mut bytes = [0u8; $len];
+                if input.read(&mut bytes) != $len {
+                    return None;
+                }
+                Some($name(bytes))
+            }
+        }
+    };
+}
+
+/// Add Parity Codec serialization support to a tuple struct wrapping an array of integers.
+///
+/// The elements are encoded one after the other as little-endian bytes, so the
+/// encoding does not depend on the byte order or the padding of the host. The
+/// element type must implement `LittleEndianInt`:
+///
+/// ```
+/// # #[cfg(feature = "parity-codec")] {
+/// struct Limbs([u32; 3]);
+///
+/// impl_codec::impl_fixed_array_codec!(Limbs, u32, 3);
+/// # }
+/// ```
+///
+/// Other element types are rejected at compile time:
+///
+/// ```compile_fail
+/// struct Flags([bool; 3]);
+///
+/// impl_codec::impl_fixed_array_codec!(Flags, bool, 3);
+/// ```
+#[macro_export]
+macro_rules! impl_fixed_array_codec {
+    ($name: ident, $int: ty, $len: expr) => {
+        impl $crate::codec::Encode for $name {
+            fn encode_to<W: $crate::codec::Output>(&self, dest: &mut W) {
+                let mut bytes = [0u8; <$int as $crate::LittleEndianInt>::SIZE];
+                for value in self.0.iter() {
+                    <$int as $crate::LittleEndianInt>::write_le(*value, &mut bytes);
+                    dest.write(&bytes);
+                }
             }
         }
+
         impl $crate::codec::Decode for $name {
             fn decode<I: $crate::codec::Input>(input: &mut I) -> Option<Self> {
-                <[u8; $len] as $crate::codec::Decode>::decode(input).map($name)
+                let mut array = [<$int as $crate::LittleEndianInt>::ZERO; $len];
+                let mut bytes = [0u8; <$int as $crate::LittleEndianInt>::SIZE];
+                for value in array.iter_mut() {
+                    if input.read(&mut bytes) != bytes.len() {
+                        return None;
+                    }
+                    *value = <$int as $crate::LittleEndianInt>::read_le(&bytes);
+                }
+                Some($name(array))
             }
         }
     };
 }
 
-/// Add Parity Codec serialization extension for some array types.
+/// Removed: use `impl_fixed_array_codec!` instead.
+///
+/// This macro copied the raw memory of the types, so their encoding depended on
+/// the byte order and the padding of the host. It keeps its grammar so that
+/// existing calls fail with an error pointing to `impl_fixed_array_codec!`:
+///
+/// ```compile_fail
+/// struct Limbs([u32; 3]);
+///
+/// impl_codec::impl_fixed_hash_codec_ext!(Limbs);
+/// ```
 #[macro_export]
 macro_rules! impl_fixed_hash_codec_ext {
-    ( $( $t:ty ),* ) => { $(
-        impl $crate::codec::Encode for $t {
+    ( $( $t:ty ),* ) => {
+        compile_error!(
+            "impl_fixed_hash_codec_ext! was removed, as its encoding depended on the host; \
+             use impl_fixed_array_codec!(Name, Int, LEN) for a tuple struct wrapping an array \
+             of integers"
+        );
+    };
+}
+
+/// Add SCALE codec serialization support to an integer created by `construct_uint!`.
+#[macro_export]
+macro_rules! impl_uint_scale_codec {
+    ($name: ident, $len: expr) => {
+        impl $crate::scale_codec::Encode for $name {
+            fn size_hint(&self) -> usize {
+                $len * 8
+            }
+
             fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
-                let size = core::mem::size_of::<$t>();
-                let value_slice = unsafe {
-                    let ptr = self as *const _ as *const u8;
-                    if size != 0 {
-                        core::slice::from_raw_parts(ptr, size)
-                    } else {
-                        &[]
+                let mut bytes = [0u8; $len * 8];
+                self.to_little_endian(&mut bytes);
+                f(&bytes)
+            }
+        }
+
+        impl $crate::scale_codec::EncodeLike for $name {}
+
+        impl $crate::MaxEncodedLen for $name {
+            fn max_encoded_len() -> usize {
+                $len * 8
+            }
+        }
+
+        impl $crate::scale_codec::Decode for $name {
+            fn decode<I: $crate::scale_codec::Input>(
+                input: &mut I,
+            ) -> Result<Self, $crate::scale_codec::Error> {
+                let mut bytes = [0u8; $len * 8];
+                input.read(&mut bytes)?;
+                Ok($name::from_little_endian(&bytes))
+            }
+        }
+
+        const _: () = {
+            // `$name` is its `[u64; $len]` field without padding.
+            let _: [(); core::mem::size_of::<$name>()] = [(); $len * 8];
+            // `SliceCodec` divides by the size.
+            let _: [(); 0] = [(); ($len == 0) as usize];
+        };
+
+        unsafe impl $crate::LittleEndianPod for $name {
+            const ZERO: Self = $name([0; $len]);
+
+            #[inline]
+            fn from_le_in_place(values: &mut [Self]) {
+                if cfg!(target_endian = "big") {
+                    for value in values {
+                        for word in value.0.iter_mut() {
+                            *word = u64::from_le(*word);
+                        }
                     }
-                };
-                f(value_slice)
+                }
             }
         }
+    };
+}
 
-        impl $crate::codec::Decode for $t {
-            fn decode<I: $crate::codec::Input>(input: &mut I) -> Option<Self> {
-                let size = core::mem::size_of::<$t>();
-                assert!(size > 0, "EndianSensitive can never be implemented for a zero-sized type.");
-                let mut val: $t = unsafe { core::mem::zeroed() };
-
-                unsafe {
-                    let raw: &mut [u8] = core::slice::from_raw_parts_mut(
-                        &mut val as *mut $t as *mut u8,
-                        size
-                    );
-                    if input.read(raw) != size { return None }
+/// Add SCALE codec serialization support to a fixed-sized hash type created by `construct_fixed_hash!`.
+///
+/// Zero-sized hashes are rejected at compile time:
+///
+/// ```compile_fail
+/// #[derive(Clone, Copy)]
+/// struct H0([u8; 0]);
+///
+/// impl_codec::impl_fixed_hash_scale_codec!(H0, 0);
+/// ```
+#[macro_export]
+macro_rules! impl_fixed_hash_scale_codec {
+    ($name: ident, $len: expr) => {
+        impl $crate::scale_codec::Encode for $name {
+            fn size_hint(&self) -> usize {
+                $len
+            }
+
+            fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+                f(&self.0)
+            }
+        }
+
+        impl $crate::scale_codec::EncodeLike for $name {}
+
+        impl $crate::MaxEncodedLen for $name {
+            fn max_encoded_len() -> usize {
+                $len
+            }
+        }
+
+        impl $crate::scale_codec::Decode for $name {
+            fn decode<I: $crate::scale_codec::Input>(
+                input: &mut I,
+            ) -> Result<Self, $crate::scale_codec::Error> {
+                let mut bytes = [0u8; $len];
+                input.read(&mut bytes)?;
+                Ok($name(bytes))
+            }
+        }
+
+        const _: () = {
+            // `$name` is its `[u8; $len]` field, which `view_slice` relies on.
+            let _: [(); core::mem::size_of::<$name>()] = [(); $len];
+            let _: [(); core::mem::align_of::<$name>()] = [(); 1];
+            // `SliceCodec` and `view_slice` divide by the size.
+            let _: [(); 0] = [(); ($len == 0) as usize];
+        };
+
+        unsafe impl $crate::LittleEndianPod for $name {
+            const ZERO: Self = $name([0; $len]);
+
+            #[inline]
+            fn from_le_in_place(_values: &mut [Self]) {}
+        }
+
+        impl $crate::SliceView for $name {
+            fn view_slice<'a>(
+                input: &mut &'a [u8],
+            ) -> Result<&'a [Self], $crate::scale_codec::Error> {
+                // `$name` has an alignment of 1 and its memory is its encoding.
+                unsafe { $crate::view_slice(input) }
+            }
+        }
+    };
+}
+
+/// Add SCALE codec serialization support to a tuple struct wrapping an array of integers.
+///
+/// The encoding is the one of `impl_fixed_array_codec!`.
+#[macro_export]
+macro_rules! impl_fixed_array_scale_codec {
+    ($name: ident, $int: ty, $len: expr) => {
+        impl $crate::scale_codec::Encode for $name {
+            fn size_hint(&self) -> usize {
+                $len * <$int as $crate::LittleEndianInt>::SIZE
+            }
+
+            fn encode_to<W: $crate::scale_codec::Output>(&self, dest: &mut W) {
+                let mut bytes = [0u8; <$int as $crate::LittleEndianInt>::SIZE];
+                for value in self.0.iter() {
+                    <$int as $crate::LittleEndianInt>::write_le(*value, &mut bytes);
+                    dest.write(&bytes);
+                }
+            }
+        }
+
+        impl $crate::scale_codec::EncodeLike for $name {}
+
+        impl $crate::MaxEncodedLen for $name {
+            fn max_encoded_len() -> usize {
+                $len * <$int as $crate::LittleEndianInt>::SIZE
+            }
+        }
+
+        impl $crate::scale_codec::Decode for $name {
+            fn decode<I: $crate::scale_codec::Input>(
+                input: &mut I,
+            ) -> Result<Self, $crate::scale_codec::Error> {
+                let mut array = [<$int as $crate::LittleEndianInt>::ZERO; $len];
+                let mut bytes = [0u8; <$int as $crate::LittleEndianInt>::SIZE];
+                for value in array.iter_mut() {
+                    input.read(&mut bytes)?;
+                    *value = <$int as $crate::LittleEndianInt>::read_le(&bytes);
                 }
-                Some(val)
+                Ok($name(array))
             }
         }
-    )* }
+    };
 }
@@ -0,0 +1,172 @@
+// Copyright 2019 Chainpool
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Bulk SCALE encoding of slices of uints and fixed hashes.
+//!
+//! `Vec<H256>` and `Vec<U256>` are encoded and decoded one element at a time,
+//! through a temporary byte array per element. The memory of a hash is its
+//! encoding, and so is the memory of a uint on little-endian targets, so
+//! `SliceCodec` produces the same bytes with one length prefix and one copy of
+//! all the elements, and `SliceView` views an encoded `Vec` of hashes in place.
+//!
+//! The orphan rules keep `Vec<T>` on the per-element path of `parity-scale-codec`,
+//! so the bulk path is only taken through these traits.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::{cmp, mem, slice};
+
+use parity_scale_codec::{Compact, Decode, Encode, Error, Input, Output};
+
+/// Bound of the allocation made before reading from an input of unknown length,
+/// like in `parity-scale-codec`, so a bogus length prefix cannot exhaust memory.
+const MAX_PREALLOCATION: usize = 4 * 1024;
+
+const NOT_ENOUGH_DATA: &str = "Not enough data to fill buffer";
+
+/// Type whose memory is its SCALE encoding, implemented by the scale codec macros.
+///
+/// # Safety
+///
+/// `Self` is not zero-sized, has no padding and every byte pattern is a valid
+/// value. On little-endian targets its memory is its encoding, and
+/// `from_le_in_place` turns memory read from an encoding into the decoded
+/// values on any target.
+#[doc(hidden)]
+pub unsafe trait LittleEndianPod: Copy + Encode {
+    /// Value the decoding buffer is filled with before reading into it.
+    const ZERO: Self;
+
+    /// Converts values whose memory was read from their encoding to the byte
+    /// order of the target.
+    fn from_le_in_place(values: &mut [Self]);
+}
+
+/// Encoding and decoding of `Vec<Self>` with one copy of all the elements.
+///
+/// The bytes are those of `Vec<Self>`: a `Compact<u32>` length followed by the
+/// encoded elements.
+///
+/// ```
+/// use impl_codec::SliceCodec;
+/// use parity_scale_codec::Encode;
+///
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// struct H32([u8; 4]);
+/// impl_codec::impl_fixed_hash_scale_codec!(H32, 4);
+///
+/// let hashes = vec![H32([1, 2, 3, 4]), H32([5, 6, 7, 8])];
+/// let encoded = H32::encode_slice(&hashes);
+/// assert_eq!(encoded, hashes.encode());
+/// assert_eq!(H32::decode_vec(&mut &encoded[..]).unwrap(), hashes);
+/// ```
+pub trait SliceCodec: Sized {
+    /// Encodes `values` like `Vec<Self>` into `dest`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` has more than `u32::max_value()` elements, like `Vec<Self>`.
+    fn encode_slice_to<W: Output>(values: &[Self], dest: &mut W);
+
+    /// Encodes `values` like `Vec<Self>`.
+    fn encode_slice(values: &[Self]) -> Vec<u8> {
+        let mut dest = Vec::new();
+        Self::encode_slice_to(values, &mut dest);
+        dest
+    }
+
+    /// Decodes a `Vec<Self>`.
+    fn decode_vec<I: Input>(input: &mut I) -> Result<Vec<Self>, Error>;
+}
+
+impl<T: LittleEndianPod> SliceCodec for T {
+    fn encode_slice_to<W: Output>(values: &[T], dest: &mut W) {
+        let len = u32::try_from(values.len()).expect("slice length must fit in u32");
+        Compact(len).encode_to(dest);
+        if cfg!(target_endian = "little") {
+            dest.write(as_bytes(values));
+        } else {
+            for value in values {
+                value.encode_to(dest);
+            }
+        }
+    }
+
+    fn encode_slice(values: &[T]) -> Vec<u8> {
+        let mut dest = Vec::with_capacity(5 + mem::size_of_val(values));
+        T::encode_slice_to(values, &mut dest);
+        dest
+    }
+
+    fn decode_vec<I: Input>(input: &mut I) -> Result<Vec<T>, Error> {
+        let len = <Compact<u32>>::decode(input)?.0 as usize;
+        let size = mem::size_of::<T>();
+        let chunk = match input.remaining_len()? {
+            Some(remaining) if remaining / size < len => return Err(NOT_ENOUGH_DATA.into()),
+            Some(_) => len,
+            None => cmp::max(MAX_PREALLOCATION / size, 1),
+        };
+        let mut values = Vec::new();
+        while values.len() < len {
+            let start = values.len();
+            values.resize(start + cmp::min(chunk, len - start), T::ZERO);
+            input.read(as_bytes_mut(&mut values[start..]))?;
+        }
+        T::from_le_in_place(&mut values);
+        Ok(values)
+    }
+}
+
+/// Zero-copy view of an encoded `Vec<Self>`, implemented by the fixed hash types.
+///
+/// ```
+/// use impl_codec::SliceView;
+/// use parity_scale_codec::Encode;
+///
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// struct H32([u8; 4]);
+/// impl_codec::impl_fixed_hash_scale_codec!(H32, 4);
+///
+/// let encoded = (vec![H32([1, 2, 3, 4])], 7u8).encode();
+/// let mut input = &encoded[..];
+/// assert_eq!(H32::view_slice(&mut input).unwrap(), &[H32([1, 2, 3, 4])]);
+/// assert_eq!(input, &[7]);
+/// ```
+pub trait SliceView: Sized {
+    /// Views the elements of the `Vec<Self>` encoded at the start of `input`
+    /// without copying them, and advances `input` past them.
+    fn view_slice<'a>(input: &mut &'a [u8]) -> Result<&'a [Self], Error>;
+}
+
+/// Views the elements of an encoded `Vec<T>` in `input`, for `SliceView`.
+///
+/// # Safety
+///
+/// `T` must have an alignment of 1 and its memory must be its encoding on any target.
+#[doc(hidden)]
+pub unsafe fn view_slice<'a, T: LittleEndianPod>(input: &mut &'a [u8]) -> Result<&'a [T], Error> {
+    let len = <Compact<u32>>::decode(input)?.0 as usize;
+    if input.len() / mem::size_of::<T>() < len {
+        return Err(NOT_ENOUGH_DATA.into());
+    }
+    let (bytes, rest) = input.split_at(len * mem::size_of::<T>());
+    *input = rest;
+    Ok(slice::from_raw_parts(bytes.as_ptr() as *const T, len))
+}
+
+fn as_bytes<T: LittleEndianPod>(values: &[T]) -> &[u8] {
+    // `T` has no padding (`LittleEndianPod`).
+    unsafe { slice::from_raw_parts(values.as_ptr() as *const u8, mem::size_of_val(values)) }
+}
+
+fn as_bytes_mut<T: LittleEndianPod>(values: &mut [T]) -> &mut [u8] {
+    // `T` has no padding and accepts any byte pattern (`LittleEndianPod`).
+    let len = mem::size_of_val(values);
+    unsafe { slice::from_raw_parts_mut(values.as_mut_ptr() as *mut u8, len) }
+}
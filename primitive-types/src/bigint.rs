@@ -0,0 +1,43 @@
+// Copyright 2019 Chainpool
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Conversions between the uint types and `num_bigint::BigUint`.
+//!
+//! Every uint converts to `BigUint` losslessly. The conversion back is a
+//! `TryFrom` returning `Error::Overflow` if the value is too large.
+
+use core::convert::TryFrom;
+
+use num_bigint::BigUint;
+
+use crate::{Error, U128, U256, U512};
+
+macro_rules! impl_bigint_conversions {
+    ($name:ident, $n_words:tt) => {
+        impl From<$name> for BigUint {
+            fn from(value: $name) -> Self {
+                BigUint::from_bytes_le(&value.to_le_bytes())
+            }
+        }
+
+        impl TryFrom<BigUint> for $name {
+            type Error = Error;
+
+            fn try_from(value: BigUint) -> Result<Self, Error> {
+                if value.bits() > $n_words * 64 {
+                    return Err(Error::Overflow);
+                }
+                Ok($name::from_little_endian(&value.to_bytes_le()))
+            }
+        }
+    };
+}
+
+impl_bigint_conversions!(U128, 2);
+impl_bigint_conversions!(U256, 4);
+impl_bigint_conversions!(U512, 8);
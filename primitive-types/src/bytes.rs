@@ -0,0 +1,89 @@
+// Copyright 2019 Chainpool
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Conversions between the hash and uint types and bytes.
+//!
+//! The `to_be_bytes`/`to_le_bytes` family of the uint types states the byte order
+//! in its name, whatever the serialization of the type itself uses.
+//!
+//! `H256::from_slice` panics and `U256::from(&[u8])` panics or zero-extends when
+//! the slice has the wrong length. The conversions here return
+//! `Error::InvalidLength` instead.
+//!
+//! The uint types can not implement `TryFrom<&[u8]>`: `From<&[u8]>` from
+//! `construct_uint!` already provides an infallible one through the blanket
+//! implementation, so they get `try_from_big_endian` and `try_from_little_endian`.
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_fixed_hash_try_from_slice {
+    ($name:ident, $n_bytes:tt) => {
+        /// Converts from exactly as many bytes as the hash has, returning
+        /// `Error::InvalidLength` otherwise.
+        impl<'a> $crate::core_::convert::TryFrom<&'a [u8]> for $name {
+            type Error = $crate::Error;
+
+            fn try_from(bytes: &'a [u8]) -> Result<Self, $crate::Error> {
+                $crate::__check_len(bytes, $n_bytes)?;
+                Ok($name::from_slice(bytes))
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_uint_bytes {
+    ($name:ident, $n_words:tt) => {
+        impl $name {
+            /// Returns the value as big-endian bytes.
+            pub fn to_be_bytes(self) -> [u8; $n_words * 8] {
+                let mut bytes = [0u8; $n_words * 8];
+                self.to_big_endian(&mut bytes);
+                bytes
+            }
+
+            /// Returns the value as little-endian bytes.
+            pub fn to_le_bytes(self) -> [u8; $n_words * 8] {
+                let mut bytes = [0u8; $n_words * 8];
+                self.to_little_endian(&mut bytes);
+                bytes
+            }
+
+            /// Creates the value from big-endian bytes.
+            pub fn from_be_bytes(bytes: [u8; $n_words * 8]) -> Self {
+                $name::from_big_endian(&bytes)
+            }
+
+            /// Creates the value from little-endian bytes.
+            pub fn from_le_bytes(bytes: [u8; $n_words * 8]) -> Self {
+                $name::from_little_endian(&bytes)
+            }
+
+            /// Converts from exactly `8 * N` big-endian bytes, where `N` is the number of words,
+            /// returning `Error::InvalidLength` otherwise.
+            ///
+            /// The uint types do not implement a fallible `TryFrom<&[u8]>` like the hash
+            /// types: the `From<&[u8]>` of `construct_uint!` already gives them an
+            /// infallible one. This and `try_from_little_endian` take its place.
+            pub fn try_from_big_endian(bytes: &[u8]) -> Result<Self, $crate::Error> {
+                $crate::__check_len(bytes, $n_words * 8)?;
+                Ok($name::from_big_endian(bytes))
+            }
+
+            /// Converts from exactly `8 * N` little-endian bytes, where `N` is the number of words,
+            /// returning `Error::InvalidLength` otherwise.
+            ///
+            /// See `try_from_big_endian` for why this is not `TryFrom<&[u8]>`.
+            pub fn try_from_little_endian(bytes: &[u8]) -> Result<Self, $crate::Error> {
+                $crate::__check_len(bytes, $n_words * 8)?;
+                Ok($name::from_little_endian(bytes))
+            }
+        }
+    };
+}
@@ -0,0 +1,99 @@
+// Copyright 2019 Chainpool
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Checked arithmetic returning the reason of a failure.
+//!
+//! The `checked_*` methods return `None` on any failure. `CheckedMath` returns
+//! `Error::Overflow`, `Error::Underflow` or `Error::DivisionByZero` instead, so
+//! the result can be propagated with `?`.
+
+use crate::{Error, U1024, U128, U256, U512};
+
+/// Arithmetic returning an `Error` instead of panicking or wrapping.
+pub trait CheckedMath: Sized + From<u8> {
+    /// Computes `self + other`, returning `Error::Overflow` if it does not fit.
+    fn try_add(self, other: Self) -> Result<Self, Error>;
+
+    /// Computes `self - other`, returning `Error::Underflow` if it is negative.
+    fn try_sub(self, other: Self) -> Result<Self, Error>;
+
+    /// Computes `self * other`, returning `Error::Overflow` if it does not fit.
+    fn try_mul(self, other: Self) -> Result<Self, Error>;
+
+    /// Computes `self / other`, returning `Error::DivisionByZero` if `other` is zero.
+    fn try_div(self, other: Self) -> Result<Self, Error>;
+
+    /// Computes `self ^ exp`, returning `Error::Overflow` if it does not fit.
+    fn try_pow(self, exp: u32) -> Result<Self, Error>;
+
+    /// Computes `-self`, returning `Error::Underflow` unless `self` is zero.
+    fn try_neg(self) -> Result<Self, Error>;
+
+    /// Sums the items, returning `Error::Overflow` as soon as the sum does not fit.
+    fn try_sum<I: IntoIterator<Item = Self>>(iter: I) -> Result<Self, Error> {
+        iter.into_iter().try_fold(Self::from(0), Self::try_add)
+    }
+
+    /// Multiplies the items, returning `Error::Overflow` as soon as the product
+    /// does not fit.
+    fn try_product<I: IntoIterator<Item = Self>>(iter: I) -> Result<Self, Error> {
+        iter.into_iter().try_fold(Self::from(1), Self::try_mul)
+    }
+}
+
+macro_rules! impl_checked_math {
+    ($name:ident, $zero:expr, $exp:ident => $to_exp:expr) => {
+        impl CheckedMath for $name {
+            #[inline]
+            fn try_add(self, other: Self) -> Result<Self, Error> {
+                self.checked_add(other).ok_or(Error::Overflow)
+            }
+
+            #[inline]
+            fn try_sub(self, other: Self) -> Result<Self, Error> {
+                self.checked_sub(other).ok_or(Error::Underflow)
+            }
+
+            #[inline]
+            fn try_mul(self, other: Self) -> Result<Self, Error> {
+                self.checked_mul(other).ok_or(Error::Overflow)
+            }
+
+            #[inline]
+            fn try_div(self, other: Self) -> Result<Self, Error> {
+                self.checked_div(other).ok_or(Error::DivisionByZero)
+            }
+
+            #[inline]
+            fn try_pow(self, $exp: u32) -> Result<Self, Error> {
+                self.checked_pow($to_exp).ok_or(Error::Overflow)
+            }
+
+            #[inline]
+            fn try_neg(self) -> Result<Self, Error> {
+                if self == $zero {
+                    Ok(self)
+                } else {
+                    Err(Error::Underflow)
+                }
+            }
+        }
+    };
+}
+
+impl_checked_math!(U128, U128::zero(), exp => U128::from(exp));
+impl_checked_math!(U256, U256::zero(), exp => U256::from(exp));
+impl_checked_math!(U512, U512::zero(), exp => U512::from(exp));
+impl_checked_math!(U1024, U1024::zero(), exp => U1024::from(exp));
+
+impl_checked_math!(u8, 0, exp => exp);
+impl_checked_math!(u16, 0, exp => exp);
+impl_checked_math!(u32, 0, exp => exp);
+impl_checked_math!(u64, 0, exp => exp);
+impl_checked_math!(u128, 0, exp => exp);
+impl_checked_math!(usize, 0, exp => exp);
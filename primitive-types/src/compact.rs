@@ -0,0 +1,166 @@
+// Copyright 2019 Chainpool
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! SCALE compact encoding of the uint types.
+//!
+//! `CompactUint<U256>` is encoded like `Compact<u128>` of `parity-scale-codec`:
+//! values below `2^30` in the one, two and four byte modes, larger values in the
+//! big-integer mode as a prefix byte holding the length followed by the
+//! little-endian bytes without the leading zeros. Where the values overlap the
+//! bytes are those of `Compact<u128>`, and decoding rejects non-canonical
+//! encodings like it does.
+//!
+//! `Compact<U256>` itself cannot be supported: the orphan rules only allow
+//! `parity-scale-codec` to implement its traits for it, and `CompactAs` maps to
+//! types it already compacts, the widest of which is `u128`. So the uint types
+//! do not implement `HasCompact` and `#[codec(compact)]` does not apply to
+//! them; use a `CompactUint` field instead.
+
+use impl_codec::scale_codec::{Decode, Encode, EncodeLike, Error, Input, Output};
+use impl_codec::MaxEncodedLen;
+
+use crate::{U128, U256, U512};
+
+/// Uint encoded in the SCALE compact format.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CompactUint<T>(pub T);
+
+impl<T> CompactUint<T> {
+    /// Returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for CompactUint<T> {
+    fn from(value: T) -> Self {
+        CompactUint(value)
+    }
+}
+
+const OUT_OF_RANGE: &str = "out of range decoding CompactUint";
+
+/// Returns the length of the compact encoding of a value of `bits` bits.
+fn compact_len(bits: usize) -> usize {
+    match bits {
+        0..=6 => 1,
+        7..=14 => 2,
+        15..=30 => 4,
+        _ => 1 + (bits + 7) / 8,
+    }
+}
+
+macro_rules! impl_compact_uint {
+    ($name:ident, $n_words:tt) => {
+        impl From<CompactUint<$name>> for $name {
+            fn from(value: CompactUint<$name>) -> Self {
+                value.0
+            }
+        }
+
+        impl Encode for CompactUint<$name> {
+            fn size_hint(&self) -> usize {
+                compact_len(self.0.bits())
+            }
+
+            fn encode_to<W: Output>(&self, dest: &mut W) {
+                let bits = self.0.bits();
+                let low = self.0.low_u64();
+                match bits {
+                    0..=6 => dest.push_byte((low as u8) << 2),
+                    7..=14 => dest.write(&((low as u16) << 2 | 0b01).to_le_bytes()),
+                    15..=30 => dest.write(&((low as u32) << 2 | 0b10).to_le_bytes()),
+                    _ => {
+                        let len = (bits + 7) / 8;
+                        let mut bytes = [0u8; $n_words * 8];
+                        self.0.to_little_endian(&mut bytes);
+                        dest.push_byte(((len - 4) << 2) as u8 | 0b11);
+                        dest.write(&bytes[..len]);
+                    }
+                }
+            }
+        }
+
+        impl EncodeLike for CompactUint<$name> {}
+
+        impl MaxEncodedLen for CompactUint<$name> {
+            fn max_encoded_len() -> usize {
+                1 + $n_words * 8
+            }
+        }
+
+        impl Decode for CompactUint<$name> {
+            fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+                let prefix = input.read_byte()?;
+                let value = match prefix & 0b11 {
+                    0b00 => $name::from(prefix >> 2),
+                    0b01 => {
+                        let value = u16::from_le_bytes([prefix, input.read_byte()?]) >> 2;
+                        if value <= 0b0011_1111 {
+                            return Err(OUT_OF_RANGE.into());
+                        }
+                        $name::from(value)
+                    }
+                    0b10 => {
+                        let mut bytes = [prefix, 0, 0, 0];
+                        input.read(&mut bytes[1..])?;
+                        let value = u32::from_le_bytes(bytes) >> 2;
+                        if value <= 0b0011_1111_1111_1111 {
+                            return Err(OUT_OF_RANGE.into());
+                        }
+                        $name::from(value)
+                    }
+                    _ => {
+                        let len = usize::from(prefix >> 2) + 4;
+                        if len > $n_words * 8 {
+                            return Err(OUT_OF_RANGE.into());
+                        }
+                        let mut bytes = [0u8; $n_words * 8];
+                        input.read(&mut bytes[..len])?;
+                        let value = $name::from_little_endian(&bytes);
+                        // The last byte is not zero and the value needs the big-integer mode.
+                        if value.bits() <= core::cmp::max(8 * (len - 1), 30) {
+                            return Err(OUT_OF_RANGE.into());
+                        }
+                        value
+                    }
+                };
+                Ok(CompactUint(value))
+            }
+        }
+    };
+}
+
+impl_compact_uint!(U128, 2);
+impl_compact_uint!(U256, 4);
+impl_compact_uint!(U512, 8);
+
+#[cfg(feature = "scale-info")]
+mod scale_info_impls {
+    use scale_info_crate::{MetaType, Type, TypeDefCompact, TypeInfo};
+
+    use super::CompactUint;
+    use crate::{U128, U256, U512};
+
+    macro_rules! impl_compact_type_info {
+        ($name:ident) => {
+            /// Described like `Compact<T>` by `scale-info`.
+            impl TypeInfo for CompactUint<$name> {
+                type Identity = Self;
+
+                fn type_info() -> Type {
+                    TypeDefCompact::new(MetaType::new::<$name>()).into()
+                }
+            }
+        };
+    }
+
+    impl_compact_type_info!(U128);
+    impl_compact_type_info!(U256);
+    impl_compact_type_info!(U512);
+}
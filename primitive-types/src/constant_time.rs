@@ -0,0 +1,151 @@
+// Copyright 2019 Chainpool
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Constant-time equality, ordering and conditional selection through the
+//! `subtle` traits.
+//!
+//! The derived `PartialEq` and `Ord` of the hash and uint types stop at the
+//! first differing byte or word, which leaks timing information about values
+//! such as MACs and keys. The implementations here always inspect every byte or
+//! word. `subtle` 2.2 has no ordering traits, so `ct_gt` and `ct_lt` are
+//! inherent methods.
+
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+use crate::{H160, H256, H512, U128, U256, U512};
+
+/// Returns whether `a > b` without branching on the operands.
+#[inline]
+fn word_gt(a: u64, b: u64) -> Choice {
+    // the borrow out of the subtraction `b - a`
+    let borrow = ((!b & a) | (!(a ^ b) & b.wrapping_sub(a))) >> 63;
+    Choice::from(borrow as u8)
+}
+
+macro_rules! impl_fixed_hash_constant_time {
+    ($name:ident, $n_bytes:tt) => {
+        impl ConstantTimeEq for $name {
+            fn ct_eq(&self, other: &Self) -> Choice {
+                self.0[..].ct_eq(&other.0[..])
+            }
+        }
+
+        impl $name {
+            /// Returns whether `self > other` in the lexicographic byte order of `Ord`,
+            /// in constant time.
+            pub fn ct_gt(&self, other: &Self) -> Choice {
+                let mut greater = Choice::from(0);
+                let mut equal = Choice::from(1);
+                for (a, b) in self.0.iter().zip(other.0.iter()) {
+                    greater |= equal & word_gt(u64::from(*a), u64::from(*b));
+                    equal &= a.ct_eq(b);
+                }
+                greater
+            }
+
+            /// Returns whether `self < other` in the lexicographic byte order of `Ord`,
+            /// in constant time.
+            pub fn ct_lt(&self, other: &Self) -> Choice {
+                other.ct_gt(self)
+            }
+        }
+
+        impl ConditionallySelectable for $name {
+            fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+                let mut bytes = [0u8; $n_bytes];
+                for (byte, (a, b)) in bytes.iter_mut().zip(a.0.iter().zip(b.0.iter())) {
+                    *byte = u8::conditional_select(a, b, choice);
+                }
+                $name(bytes)
+            }
+        }
+    };
+}
+
+macro_rules! impl_uint_constant_time {
+    ($name:ident, $n_words:tt) => {
+        impl ConstantTimeEq for $name {
+            fn ct_eq(&self, other: &Self) -> Choice {
+                self.0[..].ct_eq(&other.0[..])
+            }
+        }
+
+        impl $name {
+            /// Returns whether `self > other`, in constant time.
+            pub fn ct_gt(&self, other: &Self) -> Choice {
+                let mut greater = Choice::from(0);
+                let mut equal = Choice::from(1);
+                // the most significant word comes last
+                for (a, b) in self.0.iter().rev().zip(other.0.iter().rev()) {
+                    greater |= equal & word_gt(*a, *b);
+                    equal &= a.ct_eq(b);
+                }
+                greater
+            }
+
+            /// Returns whether `self < other`, in constant time.
+            pub fn ct_lt(&self, other: &Self) -> Choice {
+                other.ct_gt(self)
+            }
+        }
+
+        impl ConditionallySelectable for $name {
+            fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+                let mut words = [0u64; $n_words];
+                for (word, (a, b)) in words.iter_mut().zip(a.0.iter().zip(b.0.iter())) {
+                    *word = u64::conditional_select(a, b, choice);
+                }
+                $name(words)
+            }
+        }
+    };
+}
+
+impl_fixed_hash_constant_time!(H160, 20);
+impl_fixed_hash_constant_time!(H256, 32);
+impl_fixed_hash_constant_time!(H512, 64);
+
+impl_uint_constant_time!(U128, 2);
+impl_uint_constant_time!(U256, 4);
+impl_uint_constant_time!(U512, 8);
+
+/// Makes the constant-time equality of the wrapped hash or uint the `PartialEq`
+/// of a single field tuple struct, and implements `ConstantTimeEq` for it.
+///
+/// Secret-bearing newtypes should use this instead of deriving `PartialEq`.
+///
+/// ```
+/// # #[cfg(feature = "subtle")] {
+/// use primitive_types::{impl_constant_time_eq, H256};
+///
+/// struct SessionKey(H256);
+///
+/// impl_constant_time_eq!(SessionKey);
+///
+/// assert!(SessionKey(H256::repeat_byte(7)) == SessionKey(H256::repeat_byte(7)));
+/// assert!(SessionKey(H256::repeat_byte(7)) != SessionKey(H256::zero()));
+/// # }
+/// ```
+#[macro_export]
+macro_rules! impl_constant_time_eq {
+    ($name:ident) => {
+        impl $crate::subtle::ConstantTimeEq for $name {
+            fn ct_eq(&self, other: &Self) -> $crate::subtle::Choice {
+                $crate::subtle::ConstantTimeEq::ct_eq(&self.0, &other.0)
+            }
+        }
+
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                $crate::subtle::ConstantTimeEq::ct_eq(self, other).into()
+            }
+        }
+
+        impl Eq for $name {}
+    };
+}
@@ -0,0 +1,193 @@
+// Copyright 2019 Chainpool
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Macros constructing hash and uint types with the impls of this crate.
+//!
+//! The serde, codec, SCALE codec, scale-info and rlp impls follow the features
+//! of `primitive-types`, not of the crate invoking the macro. Each feature
+//! therefore comes with a helper macro which is defined empty when the feature
+//! is off.
+
+/// Constructs a fixed hash type like `H256`.
+///
+/// Besides `construct_fixed_hash!`, this implements `TryFrom<&[u8]>` and the
+/// serde, codec, SCALE codec, scale-info and rlp impls enabled in
+/// `primitive-types`. The invoking crate needs no dependency other than
+/// `primitive-types`.
+///
+/// ```
+/// primitive_types::construct_primitive_hash! {
+///     /// Compressed public key.
+///     pub struct H264(33);
+/// }
+///
+/// assert_eq!(H264::repeat_byte(2).as_bytes().len(), 33);
+/// ```
+#[macro_export]
+macro_rules! construct_primitive_hash {
+    ( $(#[$attr:meta])* $visibility:vis struct $name:ident ( $n_bytes:tt ); ) => {
+        $crate::fixed_hash::construct_fixed_hash! {
+            $(#[$attr])*
+            $visibility struct $name ($n_bytes);
+        }
+
+        $crate::impl_fixed_hash_try_from_slice!($name, $n_bytes);
+        $crate::__primitive_serde!(hash, $name, $n_bytes);
+        $crate::__primitive_codec!(hash, $name, $n_bytes);
+        $crate::__primitive_scale_codec!(hash, $name, $n_bytes);
+        $crate::__primitive_scale_info!(hash, $name, $n_bytes);
+        $crate::__primitive_rlp!(hash, $name, $n_bytes);
+    };
+}
+
+/// Constructs a uint type like `U256`, with a number of 64-bit words of at least 2.
+///
+/// Besides `construct_uint!`, this implements the byte array conversions, the
+/// conversions with `i128` and `u128`, and the serde, codec, SCALE codec,
+/// scale-info and rlp impls enabled in `primitive-types`. The invoking crate
+/// needs no dependency other than `primitive-types`.
+///
+/// ```
+/// primitive_types::construct_primitive_uint! {
+///     /// 192-bit unsigned integer.
+///     pub struct U192(3);
+/// }
+///
+/// assert_eq!(U192::from(7).to_be_bytes()[23], 7);
+/// ```
+#[macro_export]
+macro_rules! construct_primitive_uint {
+    ( $(#[$attr:meta])* $visibility:vis struct $name:ident ( $n_words:tt ); ) => {
+        $crate::uint::construct_uint! {
+            $(#[$attr])*
+            $visibility struct $name ($n_words);
+        }
+
+        $crate::impl_uint_bytes!($name, $n_words);
+        $crate::impl_native_conversions!($name);
+        $crate::__primitive_serde!(uint, $name, $n_words);
+        $crate::__primitive_codec!(uint, $name, $n_words);
+        $crate::__primitive_scale_codec!(uint, $name, $n_words);
+        $crate::__primitive_scale_info!(uint, $name, $n_words);
+        $crate::__primitive_rlp!(uint, $name, $n_words);
+    };
+}
+
+#[cfg(feature = "impl-serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __primitive_serde {
+    (hash, $name:ident, $len:tt) => {
+        $crate::impl_serde::impl_fixed_hash_serde!($name, $len);
+    };
+    (uint, $name:ident, $len:tt) => {
+        $crate::impl_serde::impl_uint_serde!($name, $len);
+    };
+}
+
+#[cfg(not(feature = "impl-serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __primitive_serde {
+    ($($tt:tt)*) => {};
+}
+
+#[cfg(feature = "codec")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __primitive_codec {
+    (hash, $name:ident, $len:tt) => {
+        $crate::impl_codec::impl_fixed_hash_codec!($name, $len);
+    };
+    (uint, $name:ident, $len:tt) => {
+        $crate::impl_codec::impl_uint_codec!($name, $len);
+    };
+}
+
+#[cfg(not(feature = "codec"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __primitive_codec {
+    ($($tt:tt)*) => {};
+}
+
+#[cfg(feature = "scale-codec")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __primitive_scale_codec {
+    (hash, $name:ident, $len:tt) => {
+        $crate::impl_codec::impl_fixed_hash_scale_codec!($name, $len);
+    };
+    (uint, $name:ident, $len:tt) => {
+        $crate::impl_codec::impl_uint_scale_codec!($name, $len);
+    };
+}
+
+#[cfg(not(feature = "scale-codec"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __primitive_scale_codec {
+    ($($tt:tt)*) => {};
+}
+
+#[cfg(feature = "scale-info")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __primitive_scale_info {
+    (hash, $name:ident, $len:tt) => {
+        $crate::__primitive_scale_info!(
+            @bytes $name, $len, "Fixed-size hash encoded as a byte array."
+        );
+    };
+    (uint, $name:ident, $len:tt) => {
+        $crate::__primitive_scale_info!(
+            @bytes $name, $len * 8, "Integer encoded as a little-endian byte array."
+        );
+    };
+    (@bytes $name:ident, $n_bytes:expr, $doc:expr) => {
+        impl $crate::scale_info::TypeInfo for $name {
+            type Identity = Self;
+
+            fn type_info() -> $crate::scale_info::Type {
+                $crate::scale_info::Type::builder()
+                    .path($crate::scale_info::Path::new(stringify!($name), module_path!()))
+                    .docs_always(&[$doc])
+                    .composite(
+                        $crate::scale_info::build::Fields::unnamed()
+                            .field(|f| f.ty::<[u8; $n_bytes]>()),
+                    )
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "scale-info"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __primitive_scale_info {
+    ($($tt:tt)*) => {};
+}
+
+#[cfg(feature = "impl-rlp")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __primitive_rlp {
+    (hash, $name:ident, $len:tt) => {
+        $crate::impl_rlp::impl_fixed_hash_rlp!($name, $len);
+    };
+    (uint, $name:ident, $len:tt) => {
+        $crate::impl_rlp::impl_uint_rlp!($name, $len);
+    };
+}
+
+#[cfg(not(feature = "impl-rlp"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __primitive_rlp {
+    ($($tt:tt)*) => {};
+}
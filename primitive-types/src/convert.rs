@@ -0,0 +1,239 @@
+// Copyright 2019 Chainpool
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Conversions between the uint, hash and native integer types.
+//!
+//! Every narrowing conversion comes as a checked `TryFrom` returning
+//! `Error::Overflow`, a `TruncatingFrom` keeping the low-order part and a
+//! `SaturatingFrom` clamping to the bounds of the target. Hashes are read as
+//! big-endian numbers, so the low-order part is the trailing bytes.
+//!
+//! `construct_uint!` and `impl_fixed_hash_conversions!` already implement some
+//! conversions, whose `TryFrom` can not be replaced:
+//!
+//! - `From<H256> for H160` truncates.
+//! - `From<i128>` for the uints panics on negative values, and `u128`/`i128`
+//!   implement `TryFrom` with a string error. The uints have `try_from_i128`,
+//!   `try_to_u128` and `try_to_i128` returning `Error` instead.
+
+use core::convert::TryFrom;
+
+use crate::{BigEndian, Error, LittleEndian, H160, H256, H512, U1024, U128, U256, U512};
+
+/// Narrowing conversion which keeps the low-order part, like `as` between
+/// primitive integers.
+pub trait TruncatingFrom<T> {
+    /// Converts, discarding the high-order part which does not fit.
+    fn truncating_from(value: T) -> Self;
+}
+
+/// Narrowing conversion which clamps to the bounds of the target type.
+pub trait SaturatingFrom<T> {
+    /// Converts, returning the closest representable value if `value` does not fit.
+    fn saturating_from(value: T) -> Self;
+}
+
+macro_rules! impl_uint_narrowing {
+    ($wide:ident, $narrow:ident, $n_narrow_words:tt) => {
+        impl TruncatingFrom<$wide> for $narrow {
+            fn truncating_from(value: $wide) -> Self {
+                let mut words = [0u64; $n_narrow_words];
+                words.copy_from_slice(&value.0[..$n_narrow_words]);
+                $narrow(words)
+            }
+        }
+
+        impl SaturatingFrom<$wide> for $narrow {
+            fn saturating_from(value: $wide) -> Self {
+                $narrow::try_from(value).unwrap_or_else(|_| $narrow::max_value())
+            }
+        }
+    };
+}
+
+impl_uint_narrowing!(U256, U128, 2);
+impl_uint_narrowing!(U512, U128, 2);
+impl_uint_narrowing!(U512, U256, 4);
+impl_uint_narrowing!(U1024, U128, 2);
+impl_uint_narrowing!(U1024, U256, 4);
+impl_uint_narrowing!(U1024, U512, 8);
+
+macro_rules! impl_hash_narrowing {
+    ($wide:ident, $narrow:ident, $n_narrow_bytes:tt) => {
+        impl TruncatingFrom<$wide> for $narrow {
+            fn truncating_from(value: $wide) -> Self {
+                $narrow::from_slice(&value.0[value.0.len() - $n_narrow_bytes..])
+            }
+        }
+
+        impl SaturatingFrom<$wide> for $narrow {
+            fn saturating_from(value: $wide) -> Self {
+                let high = &value.0[..value.0.len() - $n_narrow_bytes];
+                if high.iter().any(|byte| *byte != 0) {
+                    $narrow::repeat_byte(0xff)
+                } else {
+                    $narrow::truncating_from(value)
+                }
+            }
+        }
+    };
+}
+
+impl_hash_narrowing!(H256, H160, 20);
+impl_hash_narrowing!(H512, H256, 32);
+
+impl From<H256> for H512 {
+    fn from(value: H256) -> Self {
+        let mut ret = H512::zero();
+        ret.0[32..].copy_from_slice(&value.0);
+        ret
+    }
+}
+
+impl TryFrom<H512> for H256 {
+    type Error = Error;
+
+    fn try_from(value: H512) -> Result<Self, Error> {
+        if value.0[..32].iter().any(|byte| *byte != 0) {
+            return Err(Error::Overflow);
+        }
+        Ok(H256::truncating_from(value))
+    }
+}
+
+macro_rules! impl_hash_uint_conversions {
+    ($hash:ident, $uint:ident) => {
+        impl From<BigEndian<$uint>> for $hash {
+            fn from(value: BigEndian<$uint>) -> Self {
+                $hash(value.0.to_be_bytes())
+            }
+        }
+
+        impl From<LittleEndian<$uint>> for $hash {
+            fn from(value: LittleEndian<$uint>) -> Self {
+                $hash(value.0.to_le_bytes())
+            }
+        }
+
+        impl From<$hash> for BigEndian<$uint> {
+            fn from(value: $hash) -> Self {
+                BigEndian($uint::from_be_bytes(value.0))
+            }
+        }
+
+        impl From<$hash> for LittleEndian<$uint> {
+            fn from(value: $hash) -> Self {
+                LittleEndian($uint::from_le_bytes(value.0))
+            }
+        }
+    };
+}
+
+impl_hash_uint_conversions!(H256, U256);
+impl_hash_uint_conversions!(H512, U512);
+
+/// The address as a big-endian number.
+impl From<H160> for U256 {
+    fn from(value: H160) -> Self {
+        U256::from_big_endian(&value.0)
+    }
+}
+
+impl TryFrom<U256> for H160 {
+    type Error = Error;
+
+    fn try_from(value: U256) -> Result<Self, Error> {
+        if value.bits() > 160 {
+            return Err(Error::Overflow);
+        }
+        Ok(H160::truncating_from(value))
+    }
+}
+
+impl TruncatingFrom<U256> for H160 {
+    fn truncating_from(value: U256) -> Self {
+        H160::truncating_from(H256(value.to_be_bytes()))
+    }
+}
+
+impl SaturatingFrom<U256> for H160 {
+    fn saturating_from(value: U256) -> Self {
+        H160::try_from(value).unwrap_or_else(|_| H160::repeat_byte(0xff))
+    }
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_native_conversions {
+    ($name:ident) => {
+        impl $name {
+            /// Converts from `i128`, returning `Error::Negative` for negative values
+            /// where `From<i128>` panics.
+            pub fn try_from_i128(value: i128) -> Result<Self, $crate::Error> {
+                if value < 0 {
+                    return Err($crate::Error::Negative);
+                }
+                Ok($name::from(value as u128))
+            }
+
+            /// Converts to `u128`, returning `Error::Overflow` if the value does not fit.
+            pub fn try_to_u128(&self) -> Result<u128, $crate::Error> {
+                <u128 as $crate::core_::convert::TryFrom<$name>>::try_from(*self)
+                    .map_err(|_| $crate::Error::Overflow)
+            }
+
+            /// Converts to `i128`, returning `Error::Overflow` if the value does not fit.
+            pub fn try_to_i128(&self) -> Result<i128, $crate::Error> {
+                <i128 as $crate::core_::convert::TryFrom<$name>>::try_from(*self)
+                    .map_err(|_| $crate::Error::Overflow)
+            }
+        }
+
+        impl $crate::TruncatingFrom<i128> for $name {
+            /// Reinterprets the two's complement bits, sign-extended to the full width.
+            fn truncating_from(value: i128) -> Self {
+                let low = $name::from(value as u128);
+                if value < 0 {
+                    low | !$name::from(u128::max_value())
+                } else {
+                    low
+                }
+            }
+        }
+
+        impl $crate::SaturatingFrom<i128> for $name {
+            fn saturating_from(value: i128) -> Self {
+                $name::try_from_i128(value).unwrap_or_else(|_| $name::zero())
+            }
+        }
+
+        impl $crate::TruncatingFrom<$name> for u128 {
+            fn truncating_from(value: $name) -> Self {
+                value.low_u128()
+            }
+        }
+
+        impl $crate::SaturatingFrom<$name> for u128 {
+            fn saturating_from(value: $name) -> Self {
+                value.try_to_u128().unwrap_or_else(|_| u128::max_value())
+            }
+        }
+
+        impl $crate::TruncatingFrom<$name> for i128 {
+            fn truncating_from(value: $name) -> Self {
+                value.low_u128() as i128
+            }
+        }
+
+        impl $crate::SaturatingFrom<$name> for i128 {
+            fn saturating_from(value: $name) -> Self {
+                value.try_to_i128().unwrap_or_else(|_| i128::max_value())
+            }
+        }
+    };
+}
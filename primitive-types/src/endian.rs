@@ -0,0 +1,271 @@
+// Copyright 2019 Chainpool
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Uint wrappers which carry the byte order of their serialized form in the type.
+//!
+//! The impls of `U256` itself differ: codec is little-endian, serde and rlp are
+//! big-endian with the leading zeros stripped. `BigEndian<U256>` and
+//! `LittleEndian<U256>` are always encoded as all 32 bytes in the named order:
+//! as a byte array by codec, a byte string by rlp and a `0x`-prefixed hex
+//! string of 64 digits by serde.
+
+use crate::{U128, U256, U512};
+
+/// Uint serialized as big-endian bytes.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BigEndian<T>(pub T);
+
+/// Uint serialized as little-endian bytes.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LittleEndian<T>(pub T);
+
+impl<T> BigEndian<T> {
+    /// Returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> LittleEndian<T> {
+    /// Returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for BigEndian<T> {
+    fn from(value: T) -> Self {
+        BigEndian(value)
+    }
+}
+
+impl<T> From<T> for LittleEndian<T> {
+    fn from(value: T) -> Self {
+        LittleEndian(value)
+    }
+}
+
+macro_rules! impl_endian_conversions {
+    ($name:ident) => {
+        impl From<BigEndian<$name>> for $name {
+            fn from(value: BigEndian<$name>) -> Self {
+                value.0
+            }
+        }
+
+        impl From<LittleEndian<$name>> for $name {
+            fn from(value: LittleEndian<$name>) -> Self {
+                value.0
+            }
+        }
+    };
+}
+
+impl_endian_conversions!(U128);
+impl_endian_conversions!(U256);
+impl_endian_conversions!(U512);
+
+#[cfg(feature = "impl-serde")]
+mod serde_impls {
+    use impl_serde::serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use impl_serde::serialize::{deserialize_check_len, serialize_raw, ExpectedLen};
+
+    use super::{BigEndian, LittleEndian};
+    use crate::{U128, U256, U512};
+
+    macro_rules! impl_endian_serde {
+        ($wrapper:ident, $to_bytes:ident, $from_bytes:ident, $name:ident, $n_words:tt) => {
+            impl Serialize for $wrapper<$name> {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: Serializer,
+                {
+                    let mut slice = [0u8; 2 + 2 * $n_words * 8];
+                    serialize_raw(&mut slice, &$name::$to_bytes(self.0), serializer)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $wrapper<$name> {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    let mut bytes = [0u8; $n_words * 8];
+                    deserialize_check_len(deserializer, ExpectedLen::Exact(&mut bytes))?;
+                    Ok($wrapper($name::$from_bytes(bytes)))
+                }
+            }
+        };
+    }
+
+    impl_endian_serde!(BigEndian, to_be_bytes, from_be_bytes, U128, 2);
+    impl_endian_serde!(BigEndian, to_be_bytes, from_be_bytes, U256, 4);
+    impl_endian_serde!(BigEndian, to_be_bytes, from_be_bytes, U512, 8);
+    impl_endian_serde!(LittleEndian, to_le_bytes, from_le_bytes, U128, 2);
+    impl_endian_serde!(LittleEndian, to_le_bytes, from_le_bytes, U256, 4);
+    impl_endian_serde!(LittleEndian, to_le_bytes, from_le_bytes, U512, 8);
+}
+
+#[cfg(feature = "codec")]
+mod codec_impls {
+    use impl_codec::codec::{Decode, Encode, Input};
+
+    use super::{BigEndian, LittleEndian};
+    use crate::{U128, U256, U512};
+
+    macro_rules! impl_endian_codec {
+        ($wrapper:ident, $to_bytes:ident, $from_bytes:ident, $name:ident, $n_words:tt) => {
+            impl Encode for $wrapper<$name> {
+                fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+                    $name::$to_bytes(self.0).using_encoded(f)
+                }
+            }
+
+            impl Decode for $wrapper<$name> {
+                fn decode<I: Input>(input: &mut I) -> Option<Self> {
+                    <[u8; $n_words * 8]>::decode(input).map(|b| $wrapper($name::$from_bytes(b)))
+                }
+            }
+        };
+    }
+
+    impl_endian_codec!(BigEndian, to_be_bytes, from_be_bytes, U128, 2);
+    impl_endian_codec!(BigEndian, to_be_bytes, from_be_bytes, U256, 4);
+    impl_endian_codec!(BigEndian, to_be_bytes, from_be_bytes, U512, 8);
+    impl_endian_codec!(LittleEndian, to_le_bytes, from_le_bytes, U128, 2);
+    impl_endian_codec!(LittleEndian, to_le_bytes, from_le_bytes, U256, 4);
+    impl_endian_codec!(LittleEndian, to_le_bytes, from_le_bytes, U512, 8);
+}
+
+#[cfg(feature = "scale-codec")]
+mod scale_codec_impls {
+    use impl_codec::scale_codec::{Decode, Encode, EncodeLike, Error, Input};
+    use impl_codec::MaxEncodedLen;
+
+    use super::{BigEndian, LittleEndian};
+    use crate::{U128, U256, U512};
+
+    macro_rules! impl_endian_scale_codec {
+        ($wrapper:ident, $to_bytes:ident, $from_bytes:ident, $name:ident, $n_words:tt) => {
+            impl Encode for $wrapper<$name> {
+                fn size_hint(&self) -> usize {
+                    $n_words * 8
+                }
+
+                fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+                    f(&$name::$to_bytes(self.0))
+                }
+            }
+
+            impl EncodeLike for $wrapper<$name> {}
+
+            impl MaxEncodedLen for $wrapper<$name> {
+                fn max_encoded_len() -> usize {
+                    $n_words * 8
+                }
+            }
+
+            impl Decode for $wrapper<$name> {
+                fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+                    let mut bytes = [0u8; $n_words * 8];
+                    input.read(&mut bytes)?;
+                    Ok($wrapper($name::$from_bytes(bytes)))
+                }
+            }
+        };
+    }
+
+    impl_endian_scale_codec!(BigEndian, to_be_bytes, from_be_bytes, U128, 2);
+    impl_endian_scale_codec!(BigEndian, to_be_bytes, from_be_bytes, U256, 4);
+    impl_endian_scale_codec!(BigEndian, to_be_bytes, from_be_bytes, U512, 8);
+    impl_endian_scale_codec!(LittleEndian, to_le_bytes, from_le_bytes, U128, 2);
+    impl_endian_scale_codec!(LittleEndian, to_le_bytes, from_le_bytes, U256, 4);
+    impl_endian_scale_codec!(LittleEndian, to_le_bytes, from_le_bytes, U512, 8);
+}
+
+#[cfg(feature = "scale-info")]
+mod scale_info_impls {
+    use core::iter;
+
+    use scale_info_crate::build::Fields;
+    use scale_info_crate::{MetaType, Path, Type, TypeInfo, TypeParameter};
+
+    use super::{BigEndian, LittleEndian};
+    use crate::{U128, U256, U512};
+
+    macro_rules! impl_endian_type_info {
+        ($wrapper:ident, $doc:expr, $name:ident, $n_words:tt) => {
+            impl TypeInfo for $wrapper<$name> {
+                type Identity = Self;
+
+                fn type_info() -> Type {
+                    Type::builder()
+                        .path(Path::new(stringify!($wrapper), "primitive_types"))
+                        .type_params(iter::once(TypeParameter::new(
+                            "T",
+                            Some(MetaType::new::<$name>()),
+                        )))
+                        .docs_always(&[$doc])
+                        .composite(Fields::unnamed().field(|f| f.ty::<[u8; $n_words * 8]>()))
+                }
+            }
+        };
+    }
+
+    const BE: &str = "Uint encoded as a big-endian byte array.";
+    const LE: &str = "Uint encoded as a little-endian byte array.";
+
+    impl_endian_type_info!(BigEndian, BE, U128, 2);
+    impl_endian_type_info!(BigEndian, BE, U256, 4);
+    impl_endian_type_info!(BigEndian, BE, U512, 8);
+    impl_endian_type_info!(LittleEndian, LE, U128, 2);
+    impl_endian_type_info!(LittleEndian, LE, U256, 4);
+    impl_endian_type_info!(LittleEndian, LE, U512, 8);
+}
+
+#[cfg(feature = "impl-rlp")]
+mod rlp_impls {
+    use core::cmp::Ordering;
+
+    use impl_rlp::rlp::{Decodable, DecoderError, Encodable, Rlp, RlpStream};
+
+    use super::{BigEndian, LittleEndian};
+    use crate::{U128, U256, U512};
+
+    macro_rules! impl_endian_rlp {
+        ($wrapper:ident, $to_bytes:ident, $from_bytes:ident, $name:ident, $n_words:tt) => {
+            impl Encodable for $wrapper<$name> {
+                fn rlp_append(&self, s: &mut RlpStream) {
+                    s.encoder().encode_value(&$name::$to_bytes(self.0));
+                }
+            }
+
+            impl Decodable for $wrapper<$name> {
+                fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+                    rlp.decoder()
+                        .decode_value(|bytes| match bytes.len().cmp(&($n_words * 8)) {
+                            Ordering::Less => Err(DecoderError::RlpIsTooShort),
+                            Ordering::Greater => Err(DecoderError::RlpIsTooBig),
+                            Ordering::Equal => {
+                                let mut array = [0u8; $n_words * 8];
+                                array.copy_from_slice(bytes);
+                                Ok($wrapper($name::$from_bytes(array)))
+                            }
+                        })
+                }
+            }
+        };
+    }
+
+    impl_endian_rlp!(BigEndian, to_be_bytes, from_be_bytes, U128, 2);
+    impl_endian_rlp!(BigEndian, to_be_bytes, from_be_bytes, U256, 4);
+    impl_endian_rlp!(BigEndian, to_be_bytes, from_be_bytes, U512, 8);
+    impl_endian_rlp!(LittleEndian, to_le_bytes, from_le_bytes, U128, 2);
+    impl_endian_rlp!(LittleEndian, to_le_bytes, from_le_bytes, U256, 4);
+    impl_endian_rlp!(LittleEndian, to_le_bytes, from_le_bytes, U512, 8);
+}
@@ -0,0 +1,418 @@
+// Copyright 2019 Chainpool
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The error type of conversions, parsing and checked arithmetic.
+
+use core::fmt;
+
+/// Error type for conversion.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// Overflow encountered.
+    Overflow,
+    /// Underflow encountered.
+    Underflow,
+    /// Negative value encountered where an unsigned one was expected.
+    Negative,
+    /// Input of the wrong length.
+    InvalidLength {
+        /// The length which was expected.
+        expected: usize,
+        /// The length which was found.
+        actual: usize,
+    },
+    /// Character which is not a hex digit, at the given position of the input.
+    InvalidHexCharacter(usize),
+    /// Character which is not a decimal digit.
+    InvalidDecimalDigit,
+    /// Division or remainder by zero.
+    DivisionByZero,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Overflow => f.write_str("value overflows the target type"),
+            Error::Underflow => f.write_str("value underflows the target type"),
+            Error::Negative => f.write_str("negative value where an unsigned one was expected"),
+            Error::InvalidLength { expected, actual } => {
+                write!(f, "invalid length: expected {}, got {}", expected, actual)
+            }
+            Error::InvalidHexCharacter(position) => {
+                write!(f, "invalid hex character at position {}", position)
+            }
+            Error::InvalidDecimalDigit => f.write_str("invalid decimal digit"),
+            Error::DivisionByZero => f.write_str("division by zero"),
+        }
+    }
+}
+
+/// Checks that `bytes` is exactly `expected` bytes long, for the conversion macros.
+#[doc(hidden)]
+pub fn check_len(bytes: &[u8], expected: usize) -> Result<(), Error> {
+    if bytes.len() == expected {
+        Ok(())
+    } else {
+        Err(Error::InvalidLength {
+            expected,
+            actual: bytes.len(),
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl From<uint::FromDecStrErr> for Error {
+    fn from(err: uint::FromDecStrErr) -> Self {
+        match err {
+            uint::FromDecStrErr::InvalidCharacter => Error::InvalidDecimalDigit,
+            uint::FromDecStrErr::InvalidLength => Error::Overflow,
+        }
+    }
+}
+
+/// Variant names, in the order of their codec indices.
+#[cfg(feature = "impl-serde")]
+const VARIANTS: &[&str] = &[
+    "Overflow",
+    "Underflow",
+    "Negative",
+    "InvalidLength",
+    "InvalidHexCharacter",
+    "InvalidDecimalDigit",
+    "DivisionByZero",
+];
+
+/// Field names of the `InvalidLength` variant.
+#[cfg(feature = "impl-serde")]
+const LENGTH_FIELDS: &[&str] = &["expected", "actual"];
+
+#[cfg(feature = "impl-serde")]
+mod serde_impls {
+    use core::fmt;
+
+    use impl_serde::serde::de::{
+        self, Deserialize, Deserializer, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor,
+    };
+    use impl_serde::serde::ser::{Serialize, SerializeStructVariant, Serializer};
+
+    use super::{Error, LENGTH_FIELDS, VARIANTS};
+
+    /// Serialized the way `#[derive(Serialize)]` would, variants by name.
+    impl Serialize for Error {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            match *self {
+                Error::Overflow => serializer.serialize_unit_variant("Error", 0, VARIANTS[0]),
+                Error::Underflow => serializer.serialize_unit_variant("Error", 1, VARIANTS[1]),
+                Error::Negative => serializer.serialize_unit_variant("Error", 2, VARIANTS[2]),
+                Error::InvalidLength { expected, actual } => {
+                    let mut variant =
+                        serializer.serialize_struct_variant("Error", 3, VARIANTS[3], 2)?;
+                    variant.serialize_field(LENGTH_FIELDS[0], &expected)?;
+                    variant.serialize_field(LENGTH_FIELDS[1], &actual)?;
+                    variant.end()
+                }
+                Error::InvalidHexCharacter(position) => {
+                    serializer.serialize_newtype_variant("Error", 4, VARIANTS[4], &position)
+                }
+                Error::InvalidDecimalDigit => {
+                    serializer.serialize_unit_variant("Error", 5, VARIANTS[5])
+                }
+                Error::DivisionByZero => serializer.serialize_unit_variant("Error", 6, VARIANTS[6]),
+            }
+        }
+    }
+
+    /// Identifier given either by name or by index.
+    struct Identifier {
+        index: usize,
+    }
+
+    struct IdentifierVisitor {
+        names: &'static [&'static str],
+    }
+
+    impl<'de> Visitor<'de> for IdentifierVisitor {
+        type Value = Identifier;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "one of {:?}", self.names)
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<Identifier, E> {
+            if value < self.names.len() as u64 {
+                Ok(Identifier {
+                    index: value as usize,
+                })
+            } else {
+                Err(E::invalid_value(de::Unexpected::Unsigned(value), &self))
+            }
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<Identifier, E> {
+            match self.names.iter().position(|name| *name == value) {
+                Some(index) => Ok(Identifier { index }),
+                None => Err(E::invalid_value(de::Unexpected::Str(value), &self)),
+            }
+        }
+    }
+
+    /// Deserializes an identifier out of the given names.
+    fn identifier<'de, D: Deserializer<'de>>(
+        deserializer: D,
+        names: &'static [&'static str],
+    ) -> Result<Identifier, D::Error> {
+        deserializer.deserialize_identifier(IdentifierVisitor { names })
+    }
+
+    struct VariantSeed;
+
+    impl<'de> de::DeserializeSeed<'de> for VariantSeed {
+        type Value = Identifier;
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Identifier, D::Error> {
+            identifier(deserializer, VARIANTS)
+        }
+    }
+
+    struct FieldSeed;
+
+    impl<'de> de::DeserializeSeed<'de> for FieldSeed {
+        type Value = Identifier;
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Identifier, D::Error> {
+            identifier(deserializer, LENGTH_FIELDS)
+        }
+    }
+
+    struct LengthVisitor;
+
+    impl<'de> Visitor<'de> for LengthVisitor {
+        type Value = Error;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "the fields of an invalid length error")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Error, A::Error> {
+            let expected = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+            let actual = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+            Ok(Error::InvalidLength { expected, actual })
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Error, A::Error> {
+            let mut fields = [None, None];
+            while let Some(field) = map.next_key_seed(FieldSeed)? {
+                if fields[field.index].is_some() {
+                    return Err(de::Error::duplicate_field(LENGTH_FIELDS[field.index]));
+                }
+                fields[field.index] = Some(map.next_value()?);
+            }
+            match fields {
+                [Some(expected), Some(actual)] => Ok(Error::InvalidLength { expected, actual }),
+                [None, _] => Err(de::Error::missing_field(LENGTH_FIELDS[0])),
+                [_, None] => Err(de::Error::missing_field(LENGTH_FIELDS[1])),
+            }
+        }
+    }
+
+    struct ErrorVisitor;
+
+    impl<'de> Visitor<'de> for ErrorVisitor {
+        type Value = Error;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "a primitive types error")
+        }
+
+        fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Error, A::Error> {
+            let (variant, access) = data.variant_seed(VariantSeed)?;
+            match variant.index {
+                0 => access.unit_variant().map(|_| Error::Overflow),
+                1 => access.unit_variant().map(|_| Error::Underflow),
+                2 => access.unit_variant().map(|_| Error::Negative),
+                3 => access.struct_variant(LENGTH_FIELDS, LengthVisitor),
+                4 => access.newtype_variant().map(Error::InvalidHexCharacter),
+                5 => access.unit_variant().map(|_| Error::InvalidDecimalDigit),
+                _ => access.unit_variant().map(|_| Error::DivisionByZero),
+            }
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Error {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_enum("Error", VARIANTS, ErrorVisitor)
+        }
+    }
+}
+
+#[cfg(feature = "codec")]
+mod codec_impls {
+    use core::convert::TryFrom;
+
+    use impl_codec::codec::{Decode, Encode, Input, Output};
+
+    use super::Error;
+
+    /// Encoded as the variant index followed by the fields, with lengths and
+    /// positions as `u64`.
+    impl Encode for Error {
+        fn encode_to<T: Output>(&self, dest: &mut T) {
+            match *self {
+                Error::Overflow => dest.push_byte(0),
+                Error::Underflow => dest.push_byte(1),
+                Error::Negative => dest.push_byte(2),
+                Error::InvalidLength { expected, actual } => {
+                    dest.push_byte(3);
+                    dest.push(&(expected as u64));
+                    dest.push(&(actual as u64));
+                }
+                Error::InvalidHexCharacter(position) => {
+                    dest.push_byte(4);
+                    dest.push(&(position as u64));
+                }
+                Error::InvalidDecimalDigit => dest.push_byte(5),
+                Error::DivisionByZero => dest.push_byte(6),
+            }
+        }
+    }
+
+    fn decode_usize<I: Input>(input: &mut I) -> Option<usize> {
+        usize::try_from(u64::decode(input)?).ok()
+    }
+
+    impl Decode for Error {
+        fn decode<I: Input>(input: &mut I) -> Option<Self> {
+            match input.read_byte()? {
+                0 => Some(Error::Overflow),
+                1 => Some(Error::Underflow),
+                2 => Some(Error::Negative),
+                3 => Some(Error::InvalidLength {
+                    expected: decode_usize(input)?,
+                    actual: decode_usize(input)?,
+                }),
+                4 => Some(Error::InvalidHexCharacter(decode_usize(input)?)),
+                5 => Some(Error::InvalidDecimalDigit),
+                6 => Some(Error::DivisionByZero),
+                _ => None,
+            }
+        }
+    }
+}
+
+#[cfg(feature = "scale-codec")]
+mod scale_codec_impls {
+    use core::convert::TryFrom;
+
+    use impl_codec::scale_codec::{self, Decode, Encode, EncodeLike, Input, Output};
+    use impl_codec::MaxEncodedLen;
+
+    use super::Error;
+
+    /// Encoded like the `codec` impl, as the variant index followed by the
+    /// fields, with lengths and positions as `u64`.
+    impl Encode for Error {
+        fn encode_to<T: Output>(&self, dest: &mut T) {
+            match *self {
+                Error::Overflow => dest.push_byte(0),
+                Error::Underflow => dest.push_byte(1),
+                Error::Negative => dest.push_byte(2),
+                Error::InvalidLength { expected, actual } => {
+                    dest.push_byte(3);
+                    (expected as u64).encode_to(dest);
+                    (actual as u64).encode_to(dest);
+                }
+                Error::InvalidHexCharacter(position) => {
+                    dest.push_byte(4);
+                    (position as u64).encode_to(dest);
+                }
+                Error::InvalidDecimalDigit => dest.push_byte(5),
+                Error::DivisionByZero => dest.push_byte(6),
+            }
+        }
+    }
+
+    impl EncodeLike for Error {}
+
+    impl MaxEncodedLen for Error {
+        /// The index and the two `u64` fields of `InvalidLength`.
+        fn max_encoded_len() -> usize {
+            1 + 2 * 8
+        }
+    }
+
+    fn decode_usize<I: Input>(input: &mut I) -> Result<usize, scale_codec::Error> {
+        usize::try_from(u64::decode(input)?).map_err(|_| "usize overflow in Error".into())
+    }
+
+    impl Decode for Error {
+        fn decode<I: Input>(input: &mut I) -> Result<Self, scale_codec::Error> {
+            match input.read_byte()? {
+                0 => Ok(Error::Overflow),
+                1 => Ok(Error::Underflow),
+                2 => Ok(Error::Negative),
+                3 => Ok(Error::InvalidLength {
+                    expected: decode_usize(input)?,
+                    actual: decode_usize(input)?,
+                }),
+                4 => Ok(Error::InvalidHexCharacter(decode_usize(input)?)),
+                5 => Ok(Error::InvalidDecimalDigit),
+                6 => Ok(Error::DivisionByZero),
+                _ => Err("invalid variant index of Error".into()),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "scale-info")]
+mod scale_info_impls {
+    use scale_info_crate::build::{Fields, Variants};
+    use scale_info_crate::{Path, Type, TypeInfo};
+
+    use super::Error;
+
+    /// Lengths and positions are described as `u64`, like they are encoded.
+    impl TypeInfo for Error {
+        type Identity = Self;
+
+        fn type_info() -> Type {
+            Type::builder()
+                .path(Path::new("Error", "primitive_types"))
+                .variant(
+                    Variants::new()
+                        .variant_unit("Overflow", 0)
+                        .variant_unit("Underflow", 1)
+                        .variant_unit("Negative", 2)
+                        .variant("InvalidLength", |v| {
+                            v.index(3).fields(
+                                Fields::named()
+                                    .field(|f| f.name("expected").ty::<u64>().type_name("u64"))
+                                    .field(|f| f.name("actual").ty::<u64>().type_name("u64")),
+                            )
+                        })
+                        .variant("InvalidHexCharacter", |v| {
+                            v.index(4)
+                                .fields(Fields::unnamed().field(|f| f.ty::<u64>().type_name("u64")))
+                        })
+                        .variant_unit("InvalidDecimalDigit", 5)
+                        .variant_unit("DivisionByZero", 6),
+                )
+        }
+    }
+}
@@ -0,0 +1,534 @@
+// Copyright 2019 Chainpool
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Unsigned fixed-point decimal numbers backed by `U256`.
+//!
+//! A `FixedU256<D>` stores `value * 10^D::DECIMALS` as a `U256`. Products and
+//! quotients are computed on the full `U512` intermediate, so they only fail
+//! when the final result does not fit.
+
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::{fmt, ops, str};
+
+use crate::{Rounding, U256};
+
+/// The number of decimal digits of a `FixedU256`.
+pub trait Decimals {
+    /// Number of decimal digits after the decimal point, at most 77.
+    ///
+    /// `10^78` does not fit in a `U256`, so `FixedU256` panics with larger values.
+    const DECIMALS: u32;
+}
+
+/// Largest number of decimal digits, such that `10^DECIMALS` fits in a `U256`.
+const MAX_DECIMALS: u32 = 77;
+
+macro_rules! construct_decimals {
+    ($($(#[$attr:meta])* $name:ident = $decimals:expr;)*) => {
+        $(
+            $(#[$attr])*
+            #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+            pub struct $name;
+
+            impl Decimals for $name {
+                const DECIMALS: u32 = $decimals;
+            }
+        )*
+    };
+}
+
+construct_decimals! {
+    /// 6 decimal digits.
+    D6 = 6;
+    /// 8 decimal digits.
+    D8 = 8;
+    /// 9 decimal digits.
+    D9 = 9;
+    /// 12 decimal digits.
+    D12 = 12;
+    /// 18 decimal digits.
+    D18 = 18;
+}
+
+/// Unsigned fixed-point number with `D::DECIMALS` decimal digits.
+pub struct FixedU256<D>(U256, PhantomData<D>);
+
+impl<D: Decimals> FixedU256<D> {
+    /// Creates a fixed-point number from its raw representation, `value * 10^DECIMALS`.
+    #[inline]
+    pub fn from_inner(inner: U256) -> Self {
+        FixedU256(inner, PhantomData)
+    }
+
+    /// Returns the raw representation, `value * 10^DECIMALS`.
+    #[inline]
+    pub fn into_inner(self) -> U256 {
+        self.0
+    }
+
+    /// The raw representation of one, `10^DECIMALS`.
+    #[inline]
+    pub fn accuracy() -> U256 {
+        U256::exp10(Self::decimals() as usize)
+    }
+
+    /// Returns `D::DECIMALS`, panicking if it is out of range.
+    #[inline]
+    fn decimals() -> u32 {
+        // `D::DECIMALS` is a constant, so the check is optimized out for valid values.
+        assert!(
+            D::DECIMALS <= MAX_DECIMALS,
+            "Decimals::DECIMALS must be at most 77"
+        );
+        D::DECIMALS
+    }
+
+    /// Zero.
+    #[inline]
+    pub fn zero() -> Self {
+        Self::from_inner(U256::zero())
+    }
+
+    /// One.
+    #[inline]
+    pub fn one() -> Self {
+        Self::from_inner(Self::accuracy())
+    }
+
+    /// The largest value which can be represented by this type.
+    #[inline]
+    pub fn max_value() -> Self {
+        Self::from_inner(U256::max_value())
+    }
+
+    /// Whether this is zero.
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// Creates a fixed-point number from an integer. Returns `None` if overflow occurred.
+    pub fn checked_from_integer<T: Into<U256>>(value: T) -> Option<Self> {
+        value
+            .into()
+            .checked_mul(Self::accuracy())
+            .map(Self::from_inner)
+    }
+
+    /// Creates a fixed-point number from an integer, saturating at the maximum value.
+    pub fn saturating_from_integer<T: Into<U256>>(value: T) -> Self {
+        Self::checked_from_integer(value).unwrap_or_else(Self::max_value)
+    }
+
+    /// Creates the fixed-point number `numerator / denominator`, rounding towards zero.
+    ///
+    /// Returns `None` if `denominator` is zero or overflow occurred.
+    pub fn checked_from_rational<N: Into<U256>, M: Into<U256>>(
+        numerator: N,
+        denominator: M,
+    ) -> Option<Self> {
+        let denominator = denominator.into();
+        if denominator.is_zero() {
+            return None;
+        }
+        numerator
+            .into()
+            .mul_div_floor(Self::accuracy(), denominator)
+            .ok()
+            .map(Self::from_inner)
+    }
+
+    /// Returns the integer part, rounding towards zero.
+    #[inline]
+    pub fn trunc(self) -> U256 {
+        self.0 / Self::accuracy()
+    }
+
+    /// Returns the fractional part.
+    #[inline]
+    pub fn fract(self) -> Self {
+        Self::from_inner(self.0 % Self::accuracy())
+    }
+
+    /// Checked addition. Returns `None` if overflow occurred.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Self::from_inner)
+    }
+
+    /// Addition which saturates at the maximum value.
+    pub fn saturating_add(self, other: Self) -> Self {
+        Self::from_inner(self.0.saturating_add(other.0))
+    }
+
+    /// Checked subtraction. Returns `None` if overflow occurred.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(Self::from_inner)
+    }
+
+    /// Subtraction which saturates at zero.
+    pub fn saturating_sub(self, other: Self) -> Self {
+        Self::from_inner(self.0.saturating_sub(other.0))
+    }
+
+    /// Checked multiplication rounding as requested. Returns `None` if overflow occurred.
+    pub fn checked_mul_round(self, other: Self, rounding: Rounding) -> Option<Self> {
+        self.0
+            .mul_div(other.0, Self::accuracy(), rounding)
+            .ok()
+            .map(Self::from_inner)
+    }
+
+    /// Checked multiplication rounding towards zero. Returns `None` if overflow occurred.
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        self.checked_mul_round(other, Rounding::Floor)
+    }
+
+    /// Multiplication rounding towards zero, which saturates at the maximum value.
+    pub fn saturating_mul(self, other: Self) -> Self {
+        self.checked_mul(other).unwrap_or_else(Self::max_value)
+    }
+
+    /// Checked division rounding as requested.
+    ///
+    /// Returns `None` if `other` is zero or overflow occurred.
+    pub fn checked_div_round(self, other: Self, rounding: Rounding) -> Option<Self> {
+        if other.is_zero() {
+            return None;
+        }
+        self.0
+            .mul_div(Self::accuracy(), other.0, rounding)
+            .ok()
+            .map(Self::from_inner)
+    }
+
+    /// Checked division rounding towards zero.
+    ///
+    /// Returns `None` if `other` is zero or overflow occurred.
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        self.checked_div_round(other, Rounding::Floor)
+    }
+
+    /// Division rounding towards zero, which saturates at the maximum value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` is zero.
+    pub fn saturating_div(self, other: Self) -> Self {
+        assert!(!other.is_zero(), "division by zero");
+        self.checked_div(other).unwrap_or_else(Self::max_value)
+    }
+
+    /// Convert from a decimal string such as `"1.2345"`.
+    ///
+    /// Digits after the decimal point beyond `DECIMALS` are only accepted if they are zero.
+    pub fn from_dec_str(value: &str) -> Result<Self, uint::FromDecStrErr> {
+        let (integer, fraction) = match value.find('.') {
+            Some(pos) => (&value[..pos], Some(&value[pos + 1..])),
+            None => (value, None),
+        };
+        if integer.is_empty() || fraction.map_or(false, str::is_empty) {
+            return Err(uint::FromDecStrErr::InvalidCharacter);
+        }
+        let integer = U256::from_dec_str(integer)?;
+
+        let decimals = Self::decimals();
+        let mut inner = U256::zero();
+        let mut digits = 0;
+        for b in fraction.unwrap_or("").bytes() {
+            match b {
+                b'0'..=b'9' if digits < decimals => {
+                    inner = inner * 10 + (b - b'0');
+                    digits += 1;
+                }
+                b'0' => {}
+                b'1'..=b'9' => return Err(uint::FromDecStrErr::InvalidLength),
+                _ => return Err(uint::FromDecStrErr::InvalidCharacter),
+            }
+        }
+        let inner = inner * U256::exp10((decimals - digits) as usize);
+        integer
+            .checked_mul(Self::accuracy())
+            .and_then(|integer| integer.checked_add(inner))
+            .map(Self::from_inner)
+            .ok_or(uint::FromDecStrErr::InvalidLength)
+    }
+}
+
+impl<D> Clone for FixedU256<D> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<D> Copy for FixedU256<D> {}
+
+impl<D> PartialEq for FixedU256<D> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<D> Eq for FixedU256<D> {}
+
+impl<D> PartialOrd for FixedU256<D> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<D> Ord for FixedU256<D> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<D> Hash for FixedU256<D> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<D> Default for FixedU256<D> {
+    fn default() -> Self {
+        FixedU256(U256::zero(), PhantomData)
+    }
+}
+
+impl<D: Decimals> ops::Add for FixedU256<D> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.checked_add(other)
+            .expect("arithmetic operation overflow")
+    }
+}
+
+impl<D: Decimals> ops::AddAssign for FixedU256<D> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<D: Decimals> ops::Sub for FixedU256<D> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.checked_sub(other)
+            .expect("arithmetic operation overflow")
+    }
+}
+
+impl<D: Decimals> ops::SubAssign for FixedU256<D> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<D: Decimals> ops::Mul for FixedU256<D> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        self.checked_mul(other)
+            .expect("arithmetic operation overflow")
+    }
+}
+
+impl<D: Decimals> ops::MulAssign for FixedU256<D> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl<D: Decimals> ops::Div for FixedU256<D> {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        assert!(!other.is_zero(), "division by zero");
+        self.checked_div(other)
+            .expect("arithmetic operation overflow")
+    }
+}
+
+impl<D: Decimals> ops::DivAssign for FixedU256<D> {
+    fn div_assign(&mut self, other: Self) {
+        *self = *self / other;
+    }
+}
+
+impl<D: Decimals> fmt::Debug for FixedU256<D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+// Trailing zeros of the fractional part are omitted. Width, fill, alignment and
+// the `+` and `0` flags apply to the whole number, like for the integer types.
+impl<D: Decimals> fmt::Display for FixedU256<D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Up to 78 integer digits, the decimal point and `MAX_DECIMALS` digits.
+        const POINT: usize = 78;
+        let mut buf = [b'0'; POINT + 1 + MAX_DECIMALS as usize];
+        let ten = U256::from(10);
+        let (mut integer, mut fraction) = self.0.div_mod(Self::accuracy());
+
+        let decimals = Self::decimals() as usize;
+        for i in (POINT + 1..POINT + 1 + decimals).rev() {
+            let (rest, digit) = fraction.div_mod(ten);
+            buf[i] += digit.low_u64() as u8;
+            fraction = rest;
+        }
+        let end = buf[POINT + 1..POINT + 1 + decimals]
+            .iter()
+            .rposition(|b| *b != b'0')
+            .map_or(POINT, |i| POINT + 2 + i);
+        buf[POINT] = b'.';
+
+        let mut start = POINT;
+        loop {
+            let (rest, digit) = integer.div_mod(ten);
+            start -= 1;
+            buf[start] += digit.low_u64() as u8;
+            integer = rest;
+            if integer.is_zero() {
+                break;
+            }
+        }
+
+        let s = str::from_utf8(&buf[start..end]).map_err(|_| fmt::Error)?;
+        f.pad_integral(true, "", s)
+    }
+}
+
+impl<D: Decimals> str::FromStr for FixedU256<D> {
+    type Err = uint::FromDecStrErr;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::from_dec_str(value)
+    }
+}
+
+#[cfg(feature = "impl-serde")]
+mod serde_impls {
+    use impl_serde::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{Decimals, FixedU256};
+    use crate::U256;
+
+    impl<D: Decimals> Serialize for FixedU256<D> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            self.0.serialize(serializer)
+        }
+    }
+
+    impl<'de, D: Decimals> Deserialize<'de> for FixedU256<D> {
+        fn deserialize<De>(deserializer: De) -> Result<Self, De::Error>
+        where
+            De: Deserializer<'de>,
+        {
+            U256::deserialize(deserializer).map(Self::from_inner)
+        }
+    }
+}
+
+#[cfg(feature = "codec")]
+mod codec_impls {
+    use impl_codec::codec::{Decode, Encode, Input};
+
+    use super::{Decimals, FixedU256};
+    use crate::U256;
+
+    impl<D: Decimals> Encode for FixedU256<D> {
+        fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+            self.0.using_encoded(f)
+        }
+    }
+
+    impl<D: Decimals> Decode for FixedU256<D> {
+        fn decode<I: Input>(input: &mut I) -> Option<Self> {
+            U256::decode(input).map(Self::from_inner)
+        }
+    }
+}
+
+#[cfg(feature = "scale-codec")]
+mod scale_codec_impls {
+    use impl_codec::scale_codec::{Decode, Encode, EncodeLike, Error, Input};
+    use impl_codec::MaxEncodedLen;
+
+    use super::{Decimals, FixedU256};
+    use crate::U256;
+
+    impl<D: Decimals> Encode for FixedU256<D> {
+        fn size_hint(&self) -> usize {
+            self.0.size_hint()
+        }
+
+        fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+            self.0.using_encoded(f)
+        }
+    }
+
+    impl<D: Decimals> EncodeLike for FixedU256<D> {}
+
+    impl<D: Decimals> MaxEncodedLen for FixedU256<D> {
+        fn max_encoded_len() -> usize {
+            U256::max_encoded_len()
+        }
+    }
+
+    impl<D: Decimals> Decode for FixedU256<D> {
+        fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+            U256::decode(input).map(Self::from_inner)
+        }
+    }
+}
+
+#[cfg(feature = "scale-info")]
+mod scale_info_impls {
+    use scale_info_crate::build::Fields;
+    use scale_info_crate::{Path, Type, TypeInfo};
+
+    use super::{Decimals, FixedU256};
+    use crate::U256;
+
+    impl<D: Decimals + 'static> TypeInfo for FixedU256<D> {
+        type Identity = Self;
+
+        fn type_info() -> Type {
+            Type::builder()
+                .path(Path::new("FixedU256", "primitive_types"))
+                .docs_always(&["Fixed-point decimal encoded as its scaled `U256` value."])
+                .composite(Fields::unnamed().field(|f| f.ty::<U256>().type_name("U256")))
+        }
+    }
+}
+
+#[cfg(feature = "impl-rlp")]
+mod rlp_impls {
+    use impl_rlp::rlp::{Decodable, DecoderError, Encodable, Rlp, RlpStream};
+
+    use super::{Decimals, FixedU256};
+    use crate::U256;
+
+    impl<D: Decimals> Encodable for FixedU256<D> {
+        fn rlp_append(&self, s: &mut RlpStream) {
+            self.0.rlp_append(s)
+        }
+    }
+
+    impl<D: Decimals> Decodable for FixedU256<D> {
+        fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+            U256::decode(rlp).map(Self::from_inner)
+        }
+    }
+}
@@ -0,0 +1,726 @@
+// Copyright 2019 Chainpool
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Signed integer types `I128`, `I256` and `I512`.
+//!
+//! Values are stored in two's complement form as little-endian words, the same
+//! layout `construct_uint!` uses for the unsigned types, so the byte level
+//! representation (and therefore the codec, serde and rlp encoding) of a
+//! non-negative signed value is identical to the one of its unsigned counterpart.
+
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::{fmt, ops, str};
+
+use crate::{Error, U128, U256, U512};
+
+macro_rules! impl_map_from {
+    ($name:ident, $from:ty, $to:ty) => {
+        impl From<$from> for $name {
+            fn from(value: $from) -> $name {
+                From::from(value as $to)
+            }
+        }
+    };
+}
+
+macro_rules! construct_int {
+    ( $(#[$attr:meta])* pub struct $name:ident ( $uint:ident, $n_words:tt ); ) => {
+        $(#[$attr])*
+        #[derive(Copy, Clone, Eq, PartialEq, Hash, Default)]
+        pub struct $name(pub [u64; $n_words]);
+
+        impl $name {
+            const SIGN_BIT: u64 = 1 << 63;
+
+            /// Zero (additive identity) of this type.
+            #[inline]
+            pub const fn zero() -> Self {
+                $name([0; $n_words])
+            }
+
+            /// One (multiplicative identity) of this type.
+            #[inline]
+            pub fn one() -> Self {
+                Self::from_bits($uint::one())
+            }
+
+            /// The largest value which can be represented by this type.
+            #[inline]
+            pub fn max_value() -> Self {
+                let mut ret = [u64::max_value(); $n_words];
+                ret[$n_words - 1] = !Self::SIGN_BIT;
+                $name(ret)
+            }
+
+            /// The smallest value which can be represented by this type.
+            #[inline]
+            pub fn min_value() -> Self {
+                let mut ret = [0; $n_words];
+                ret[$n_words - 1] = Self::SIGN_BIT;
+                $name(ret)
+            }
+
+            /// Reinterprets the two's complement bits of an unsigned integer as a signed one.
+            #[inline]
+            pub fn from_bits(bits: $uint) -> Self {
+                $name(bits.0)
+            }
+
+            /// Returns the two's complement bits of the number as an unsigned integer.
+            #[inline]
+            pub fn to_bits(self) -> $uint {
+                $uint(self.0)
+            }
+
+            /// Whether this is zero.
+            #[inline]
+            pub fn is_zero(&self) -> bool {
+                self.0.iter().all(|w| *w == 0)
+            }
+
+            /// Whether this is strictly less than zero.
+            #[inline]
+            pub fn is_negative(&self) -> bool {
+                self.0[$n_words - 1] & Self::SIGN_BIT != 0
+            }
+
+            /// Whether this is strictly greater than zero.
+            #[inline]
+            pub fn is_positive(&self) -> bool {
+                !self.is_negative() && !self.is_zero()
+            }
+
+            /// Returns `-1`, `0` or `1` depending on the sign of the number.
+            pub fn signum(&self) -> Self {
+                if self.is_negative() {
+                    -Self::one()
+                } else if self.is_zero() {
+                    Self::zero()
+                } else {
+                    Self::one()
+                }
+            }
+
+            /// Returns the absolute value.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `self` is the minimum value, whose absolute value is not representable.
+            pub fn abs(self) -> Self {
+                if self.is_negative() {
+                    -self
+                } else {
+                    self
+                }
+            }
+
+            /// Returns the absolute value as an unsigned integer, which never overflows.
+            pub fn unsigned_abs(self) -> $uint {
+                if self.is_negative() {
+                    self.wrapping_neg().to_bits()
+                } else {
+                    self.to_bits()
+                }
+            }
+
+            /// Return the least number of bits needed to represent the two's complement bits
+            /// of the number, which is the full width for negative numbers.
+            #[inline]
+            pub fn bits(&self) -> usize {
+                self.to_bits().bits()
+            }
+
+            /// Write the two's complement bits to the slice in big-endian format.
+            #[inline]
+            pub fn to_big_endian(&self, bytes: &mut [u8]) {
+                self.to_bits().to_big_endian(bytes)
+            }
+
+            /// Write the two's complement bits to the slice in little-endian format.
+            #[inline]
+            pub fn to_little_endian(&self, bytes: &mut [u8]) {
+                self.to_bits().to_little_endian(bytes)
+            }
+
+            /// Converts from big-endian two's complement bytes in memory.
+            ///
+            /// Slices shorter than the width of the type are zero-extended.
+            pub fn from_big_endian(slice: &[u8]) -> Self {
+                Self::from_bits($uint::from_big_endian(slice))
+            }
+
+            /// Converts from little-endian two's complement bytes in memory.
+            ///
+            /// Slices shorter than the width of the type are zero-extended.
+            pub fn from_little_endian(slice: &[u8]) -> Self {
+                Self::from_bits($uint::from_little_endian(slice))
+            }
+
+            /// Convert from a decimal string with an optional leading `-` or `+` sign.
+            pub fn from_dec_str(value: &str) -> Result<Self, uint::FromDecStrErr> {
+                let (negative, digits) = match value.as_bytes().first() {
+                    Some(b'-') => (true, &value[1..]),
+                    Some(b'+') => (false, &value[1..]),
+                    _ => (false, value),
+                };
+                if digits.is_empty() {
+                    return Err(uint::FromDecStrErr::InvalidCharacter);
+                }
+                let magnitude = $uint::from_dec_str(digits)?;
+                Self::from_sign_and_magnitude(negative, magnitude)
+                    .ok_or(uint::FromDecStrErr::InvalidLength)
+            }
+
+            fn from_sign_and_magnitude(negative: bool, magnitude: $uint) -> Option<Self> {
+                let value = Self::from_bits(magnitude);
+                if !value.is_negative() {
+                    Some(if negative { value.wrapping_neg() } else { value })
+                } else if negative && value == Self::min_value() {
+                    Some(value)
+                } else {
+                    None
+                }
+            }
+
+            /// Addition which wraps around at the boundary of the type.
+            #[inline]
+            pub fn wrapping_add(self, other: Self) -> Self {
+                self.overflowing_add(other).0
+            }
+
+            /// Add with overflow, returning a flag if it does.
+            pub fn overflowing_add(self, other: Self) -> (Self, bool) {
+                let res = Self::from_bits(self.to_bits().overflowing_add(other.to_bits()).0);
+                let overflow = self.is_negative() == other.is_negative()
+                    && res.is_negative() != self.is_negative();
+                (res, overflow)
+            }
+
+            /// Checked addition. Returns `None` if overflow occurred.
+            pub fn checked_add(self, other: Self) -> Option<Self> {
+                match self.overflowing_add(other) {
+                    (_, true) => None,
+                    (val, false) => Some(val),
+                }
+            }
+
+            /// Addition which saturates at the minimum or maximum value.
+            pub fn saturating_add(self, other: Self) -> Self {
+                match self.overflowing_add(other) {
+                    (_, true) => self.saturated(),
+                    (val, false) => val,
+                }
+            }
+
+            /// Subtraction which wraps around at the boundary of the type.
+            #[inline]
+            pub fn wrapping_sub(self, other: Self) -> Self {
+                self.overflowing_sub(other).0
+            }
+
+            /// Subtract with overflow, returning a flag if it does.
+            pub fn overflowing_sub(self, other: Self) -> (Self, bool) {
+                let res = Self::from_bits(self.to_bits().overflowing_sub(other.to_bits()).0);
+                let overflow = self.is_negative() != other.is_negative()
+                    && res.is_negative() != self.is_negative();
+                (res, overflow)
+            }
+
+            /// Checked subtraction. Returns `None` if overflow occurred.
+            pub fn checked_sub(self, other: Self) -> Option<Self> {
+                match self.overflowing_sub(other) {
+                    (_, true) => None,
+                    (val, false) => Some(val),
+                }
+            }
+
+            /// Subtraction which saturates at the minimum or maximum value.
+            pub fn saturating_sub(self, other: Self) -> Self {
+                match self.overflowing_sub(other) {
+                    (_, true) => self.saturated(),
+                    (val, false) => val,
+                }
+            }
+
+            /// Multiplication which wraps around at the boundary of the type.
+            #[inline]
+            pub fn wrapping_mul(self, other: Self) -> Self {
+                self.overflowing_mul(other).0
+            }
+
+            /// Multiply with overflow, returning a flag if it does.
+            pub fn overflowing_mul(self, other: Self) -> (Self, bool) {
+                // the low bits of a two's complement product do not depend on the signs
+                let res = Self::from_bits(self.to_bits().overflowing_mul(other.to_bits()).0);
+                let negative = self.is_negative() != other.is_negative();
+                let (magnitude, overflow) =
+                    self.unsigned_abs().overflowing_mul(other.unsigned_abs());
+                let overflow = overflow || Self::from_sign_and_magnitude(negative, magnitude).is_none();
+                (res, overflow)
+            }
+
+            /// Checked multiplication. Returns `None` if overflow occurred.
+            pub fn checked_mul(self, other: Self) -> Option<Self> {
+                match self.overflowing_mul(other) {
+                    (_, true) => None,
+                    (val, false) => Some(val),
+                }
+            }
+
+            /// Multiplication which saturates at the minimum or maximum value.
+            pub fn saturating_mul(self, other: Self) -> Self {
+                match self.overflowing_mul(other) {
+                    (_, true) if self.is_negative() != other.is_negative() => Self::min_value(),
+                    (_, true) => Self::max_value(),
+                    (val, false) => val,
+                }
+            }
+
+            /// Returns a pair `(self / other, self % other)`, rounding the quotient towards
+            /// zero and giving the remainder the sign of `self`, as the primitive integers do.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `other` is zero or if the quotient overflows (`min_value() / -1`).
+            pub fn div_mod(self, other: Self) -> (Self, Self) {
+                let (q, r) = self.unsigned_abs().div_mod(other.unsigned_abs());
+                let q = Self::from_sign_and_magnitude(self.is_negative() != other.is_negative(), q)
+                    .expect("arithmetic operation overflow");
+                let r = Self::from_sign_and_magnitude(self.is_negative(), r)
+                    .expect("remainder is smaller than the dividend; qed");
+                (q, r)
+            }
+
+            /// Checked division. Returns `None` if `other == 0` or the quotient overflows.
+            pub fn checked_div(self, other: Self) -> Option<Self> {
+                if other.is_zero() || (self == Self::min_value() && other == -Self::one()) {
+                    None
+                } else {
+                    Some(self.div_mod(other).0)
+                }
+            }
+
+            /// Checked remainder. Returns `None` if `other == 0` or the quotient overflows.
+            pub fn checked_rem(self, other: Self) -> Option<Self> {
+                if other.is_zero() || (self == Self::min_value() && other == -Self::one()) {
+                    None
+                } else {
+                    Some(self.div_mod(other).1)
+                }
+            }
+
+            /// Negation which wraps around at the boundary of the type.
+            #[inline]
+            pub fn wrapping_neg(self) -> Self {
+                self.overflowing_neg().0
+            }
+
+            /// Negate with overflow, returning a flag if it does.
+            ///
+            /// The only value which overflows is the minimum value, which is returned unchanged.
+            pub fn overflowing_neg(self) -> (Self, bool) {
+                let res = Self::from_bits((!self.to_bits()).overflowing_add($uint::one()).0);
+                (res, self == Self::min_value())
+            }
+
+            /// Checked negation. Returns `None` if overflow occurred.
+            pub fn checked_neg(self) -> Option<Self> {
+                match self.overflowing_neg() {
+                    (_, true) => None,
+                    (val, false) => Some(val),
+                }
+            }
+
+            /// Fast exponentiation by squaring. Returns result and overflow flag.
+            pub fn overflowing_pow(self, expon: u32) -> (Self, bool) {
+                let res = Self::from_bits(self.to_bits().overflowing_pow($uint::from(expon)).0);
+                let negative = self.is_negative() && expon % 2 == 1;
+                let overflow = match self.unsigned_abs().checked_pow($uint::from(expon)) {
+                    Some(magnitude) => Self::from_sign_and_magnitude(negative, magnitude).is_none(),
+                    None => true,
+                };
+                (res, overflow)
+            }
+
+            /// Checked exponentiation. Returns `None` if overflow occurred.
+            pub fn checked_pow(self, expon: u32) -> Option<Self> {
+                match self.overflowing_pow(expon) {
+                    (_, true) => None,
+                    (val, false) => Some(val),
+                }
+            }
+
+            /// Fast exponentiation by squaring.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the result overflows the type.
+            pub fn pow(self, expon: u32) -> Self {
+                self.checked_pow(expon).expect("arithmetic operation overflow")
+            }
+
+            // The word a negative (all ones) or non-negative (all zeros) number is sign-extended with.
+            fn sign_word(&self) -> u64 {
+                if self.is_negative() {
+                    u64::max_value()
+                } else {
+                    0
+                }
+            }
+
+            fn saturated(self) -> Self {
+                if self.is_negative() {
+                    Self::min_value()
+                } else {
+                    Self::max_value()
+                }
+            }
+        }
+
+        impl From<i64> for $name {
+            fn from(value: i64) -> $name {
+                let fill = if value < 0 { u64::max_value() } else { 0 };
+                let mut ret = [fill; $n_words];
+                ret[0] = value as u64;
+                $name(ret)
+            }
+        }
+
+        impl From<i128> for $name {
+            fn from(value: i128) -> $name {
+                let fill = if value < 0 { u64::max_value() } else { 0 };
+                let mut ret = [fill; $n_words];
+                ret[0] = value as u64;
+                ret[1] = (value >> 64) as u64;
+                $name(ret)
+            }
+        }
+
+        impl From<u64> for $name {
+            fn from(value: u64) -> $name {
+                let mut ret = [0; $n_words];
+                ret[0] = value;
+                $name(ret)
+            }
+        }
+
+        impl_map_from!($name, i8, i64);
+        impl_map_from!($name, i16, i64);
+        impl_map_from!($name, i32, i64);
+        impl_map_from!($name, isize, i64);
+        impl_map_from!($name, u8, u64);
+        impl_map_from!($name, u16, u64);
+        impl_map_from!($name, u32, u64);
+        impl_map_from!($name, usize, u64);
+
+        impl TryFrom<$name> for i64 {
+            type Error = Error;
+
+            fn try_from(value: $name) -> Result<i64, Error> {
+                let fill = value.sign_word();
+                let low = value.0[0] as i64;
+                if value.0[1..].iter().any(|w| *w != fill) || (low < 0) != value.is_negative() {
+                    return Err(Error::Overflow);
+                }
+                Ok(low)
+            }
+        }
+
+        impl TryFrom<$name> for i128 {
+            type Error = Error;
+
+            fn try_from(value: $name) -> Result<i128, Error> {
+                let fill = value.sign_word();
+                let low = i128::from(value.0[0]) | (i128::from(value.0[1]) << 64);
+                if value.0[2..].iter().any(|w| *w != fill) || (low < 0) != value.is_negative() {
+                    return Err(Error::Overflow);
+                }
+                Ok(low)
+            }
+        }
+
+        impl TryFrom<$uint> for $name {
+            type Error = Error;
+
+            fn try_from(value: $uint) -> Result<$name, Error> {
+                let ret = $name::from_bits(value);
+                if ret.is_negative() {
+                    return Err(Error::Overflow);
+                }
+                Ok(ret)
+            }
+        }
+
+        impl TryFrom<$name> for $uint {
+            type Error = Error;
+
+            fn try_from(value: $name) -> Result<$uint, Error> {
+                if value.is_negative() {
+                    return Err(Error::Negative);
+                }
+                Ok(value.to_bits())
+            }
+        }
+
+        // Converts from big endian representation.
+        impl<'a> From<&'a [u8]> for $name {
+            fn from(bytes: &[u8]) -> $name {
+                Self::from_big_endian(bytes)
+            }
+        }
+
+        impl Ord for $name {
+            fn cmp(&self, other: &$name) -> Ordering {
+                match (self.is_negative(), other.is_negative()) {
+                    (true, false) => Ordering::Less,
+                    (false, true) => Ordering::Greater,
+                    // two's complement preserves the order of numbers with the same sign
+                    _ => self.to_bits().cmp(&other.to_bits()),
+                }
+            }
+        }
+
+        impl PartialOrd for $name {
+            fn partial_cmp(&self, other: &$name) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl<T> ops::Add<T> for $name where T: Into<$name> {
+            type Output = $name;
+
+            fn add(self, other: T) -> $name {
+                self.checked_add(other.into()).expect("arithmetic operation overflow")
+            }
+        }
+
+        impl<T> ops::AddAssign<T> for $name where T: Into<$name> {
+            fn add_assign(&mut self, other: T) {
+                *self = *self + other;
+            }
+        }
+
+        impl<T> ops::Sub<T> for $name where T: Into<$name> {
+            type Output = $name;
+
+            fn sub(self, other: T) -> $name {
+                self.checked_sub(other.into()).expect("arithmetic operation overflow")
+            }
+        }
+
+        impl<T> ops::SubAssign<T> for $name where T: Into<$name> {
+            fn sub_assign(&mut self, other: T) {
+                *self = *self - other;
+            }
+        }
+
+        impl<T> ops::Mul<T> for $name where T: Into<$name> {
+            type Output = $name;
+
+            fn mul(self, other: T) -> $name {
+                self.checked_mul(other.into()).expect("arithmetic operation overflow")
+            }
+        }
+
+        impl<T> ops::MulAssign<T> for $name where T: Into<$name> {
+            fn mul_assign(&mut self, other: T) {
+                *self = *self * other;
+            }
+        }
+
+        impl<T> ops::Div<T> for $name where T: Into<$name> {
+            type Output = $name;
+
+            fn div(self, other: T) -> $name {
+                self.div_mod(other.into()).0
+            }
+        }
+
+        impl<T> ops::DivAssign<T> for $name where T: Into<$name> {
+            fn div_assign(&mut self, other: T) {
+                *self = *self / other;
+            }
+        }
+
+        impl<T> ops::Rem<T> for $name where T: Into<$name> {
+            type Output = $name;
+
+            fn rem(self, other: T) -> $name {
+                self.div_mod(other.into()).1
+            }
+        }
+
+        impl<T> ops::RemAssign<T> for $name where T: Into<$name> {
+            fn rem_assign(&mut self, other: T) {
+                *self = *self % other;
+            }
+        }
+
+        impl ops::Neg for $name {
+            type Output = $name;
+
+            fn neg(self) -> $name {
+                self.checked_neg().expect("arithmetic operation overflow")
+            }
+        }
+
+        impl ops::Not for $name {
+            type Output = $name;
+
+            fn not(self) -> $name {
+                Self::from_bits(!self.to_bits())
+            }
+        }
+
+        impl ops::BitAnd<$name> for $name {
+            type Output = $name;
+
+            fn bitand(self, other: $name) -> $name {
+                Self::from_bits(self.to_bits() & other.to_bits())
+            }
+        }
+
+        impl ops::BitOr<$name> for $name {
+            type Output = $name;
+
+            fn bitor(self, other: $name) -> $name {
+                Self::from_bits(self.to_bits() | other.to_bits())
+            }
+        }
+
+        impl ops::BitXor<$name> for $name {
+            type Output = $name;
+
+            fn bitxor(self, other: $name) -> $name {
+                Self::from_bits(self.to_bits() ^ other.to_bits())
+            }
+        }
+
+        impl ops::Shl<usize> for $name {
+            type Output = $name;
+
+            fn shl(self, shift: usize) -> $name {
+                Self::from_bits(self.to_bits() << shift)
+            }
+        }
+
+        impl ops::ShlAssign<usize> for $name {
+            fn shl_assign(&mut self, shift: usize) {
+                *self = *self << shift;
+            }
+        }
+
+        // Arithmetic shift, the sign bit is propagated.
+        impl ops::Shr<usize> for $name {
+            type Output = $name;
+
+            fn shr(self, shift: usize) -> $name {
+                if self.is_negative() {
+                    !Self::from_bits((!self.to_bits()) >> shift)
+                } else {
+                    Self::from_bits(self.to_bits() >> shift)
+                }
+            }
+        }
+
+        impl ops::ShrAssign<usize> for $name {
+            fn shr_assign(&mut self, shift: usize) {
+                *self = *self >> shift;
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Display::fmt(self, f)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                if self.is_negative() {
+                    write!(f, "-")?;
+                }
+                fmt::Display::fmt(&self.unsigned_abs(), f)
+            }
+        }
+
+        // Formats the two's complement bits, as the primitive integers do.
+        impl fmt::LowerHex for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::LowerHex::fmt(&self.to_bits(), f)
+            }
+        }
+
+        impl str::FromStr for $name {
+            type Err = uint::FromDecStrErr;
+
+            fn from_str(value: &str) -> Result<$name, Self::Err> {
+                $name::from_dec_str(value)
+            }
+        }
+    };
+}
+
+/// Implements the lossless widening and the checked narrowing conversions
+/// between signed integers of different width, and from narrower unsigned
+/// integers to wider signed integers.
+macro_rules! impl_int_conversions {
+    ($narrow:ident, $narrow_uint:ident, $narrow_words:expr, $wide:ident, $wide_words:expr) => {
+        impl From<$narrow> for $wide {
+            fn from(value: $narrow) -> $wide {
+                let fill = value.sign_word();
+                let mut ret = [fill; $wide_words];
+                ret[..$narrow_words].copy_from_slice(&value.0);
+                $wide(ret)
+            }
+        }
+
+        impl From<$narrow_uint> for $wide {
+            fn from(value: $narrow_uint) -> $wide {
+                let mut ret = [0; $wide_words];
+                ret[..$narrow_words].copy_from_slice(&value.0);
+                $wide(ret)
+            }
+        }
+
+        impl TryFrom<$wide> for $narrow {
+            type Error = Error;
+
+            fn try_from(value: $wide) -> Result<$narrow, Error> {
+                let fill = value.sign_word();
+                let mut ret = [0; $narrow_words];
+                ret.copy_from_slice(&value.0[..$narrow_words]);
+                let ret = $narrow(ret);
+                if value.0[$narrow_words..].iter().any(|w| *w != fill)
+                    || ret.is_negative() != value.is_negative()
+                {
+                    return Err(Error::Overflow);
+                }
+                Ok(ret)
+            }
+        }
+    };
+}
+
+construct_int! {
+    /// 128-bit signed integer.
+    pub struct I128(U128, 2);
+}
+construct_int! {
+    /// 256-bit signed integer.
+    pub struct I256(U256, 4);
+}
+construct_int! {
+    /// 512-bits signed integer.
+    pub struct I512(U512, 8);
+}
+
+impl_int_conversions!(I128, U128, 2, I256, 4);
+impl_int_conversions!(I128, U128, 2, I512, 8);
+impl_int_conversions!(I256, U256, 4, I512, 8);
@@ -8,9 +8,42 @@
 
 //! Primitive types shared by Substrate and Parity Ethereum.
 //!
-//! Those are uint types `U128`, `U256` and `U512`, and fixed hash types `H160`,
-//! `H256` and `H512`, with optional serde serialization, parity-scale-codec and
-//! rlp encoding.
+//! Those are uint types `U128`, `U256`, `U512` and `U1024`, signed int types
+//! `I128`, `I256` and `I512`, and fixed hash types `H160`, `H256` and `H512`,
+//! with optional serde serialization, parity codec and rlp encoding.
+//!
+//! The `codec` feature implements the `Option`-based codec of `parity-codec` 3,
+//! the `scale-codec` feature the `Result`-based codec of `parity-scale-codec` 1
+//! and the `MaxEncodedLen` trait of `impl-codec`. Both encode the same bytes and
+//! can be enabled together. With `scale-codec`, `CompactUint` encodes the uint
+//! types in the SCALE compact format, `SliceCodec` encodes and decodes a `Vec`
+//! of uints or hashes with one copy of all the elements, and `SliceView` views
+//! an encoded `Vec` of hashes without copying.
+//!
+//! The `scale-info` feature describes the types for `scale-info` 1.0: the uints
+//! and ints as little-endian byte arrays, the hashes as byte arrays.
+//! `scale-info` needs rustc 1.51 or later.
+//!
+//! With the `subtle` feature the hash and uint types implement the constant-time
+//! comparison and selection traits of the `subtle` crate.
+//!
+//! With the `num-traits` feature the uint types implement the numeric traits of
+//! the `num-traits` crate, so they can be used in generic numeric code. The
+//! `num-bigint` feature, which depends on `std`, adds conversions between the
+//! uint types and `BigUint`.
+//!
+//! The `upstream-primitive-types` and `ethereum-types` features, which depend on
+//! `std`, convert the hash and uint types to and from their twins in parity's
+//! `primitive-types` 0.7 and `ethereum-types` 0.9.
+//!
+//! The fixed-point decimal type `FixedU256` is built on top of `U256`.
+//!
+//! Downstream crates can define further widths with `construct_primitive_hash!`
+//! and `construct_primitive_uint!`, which add the impls enabled here.
+//!
+//! Every width is a separate type generated by `construct_uint!` or
+//! `construct_fixed_hash!`, and the conversions between widths come from a
+//! single macro.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(
@@ -23,53 +56,115 @@
 #[cfg(not(feature = "std"))]
 extern crate alloc;
 
+#[doc(hidden)]
 #[macro_use]
-extern crate fixed_hash;
+pub extern crate fixed_hash;
+#[doc(hidden)]
 #[macro_use]
-extern crate uint;
-
+pub extern crate uint;
+
+#[cfg(feature = "num-bigint")]
+mod bigint;
+mod bytes;
+mod checked;
+#[cfg(feature = "scale-codec")]
+mod compact;
+#[cfg(feature = "subtle")]
+mod constant_time;
+mod construct;
+mod convert;
+mod endian;
+mod error;
+mod fixed;
+mod int;
+mod math;
+mod mixed;
+mod modular;
+mod montgomery;
+mod mul_div;
+#[cfg(feature = "num-traits")]
+mod num;
 mod tests;
+#[cfg(any(feature = "upstream-primitive-types", feature = "ethereum-types"))]
+mod upstream;
+mod wrapping;
 
 use core::convert::TryFrom;
 
+#[doc(hidden)]
+pub use core as core_;
+#[doc(hidden)]
+pub use error::check_len as __check_len;
+#[cfg(feature = "impl-codec")]
+#[doc(hidden)]
+pub use impl_codec;
+#[cfg(feature = "impl-rlp")]
+#[doc(hidden)]
+pub use impl_rlp;
 #[cfg(feature = "impl-serde")]
-use impl_serde::{impl_fixed_hash_serde, impl_uint_serde};
+#[doc(hidden)]
+pub use impl_serde;
+#[cfg(feature = "num-bigint")]
+pub use num_bigint;
+#[cfg(feature = "num-traits")]
+pub use num_traits;
+#[cfg(feature = "scale-info")]
+pub use scale_info_crate as scale_info;
+#[cfg(feature = "subtle")]
+pub use subtle;
 
-#[cfg(feature = "impl-codec")]
-use impl_codec::{impl_fixed_hash_codec, impl_uint_codec};
+#[cfg(feature = "impl-serde")]
+use impl_serde::impl_uint_serde;
 
-#[cfg(feature = "impl-rlp")]
-use impl_rlp::{impl_fixed_hash_rlp, impl_uint_rlp};
+#[cfg(feature = "codec")]
+use impl_codec::impl_uint_codec;
 
-/// Error type for conversion.
-#[derive(Debug, PartialEq, Eq)]
-pub enum Error {
-    /// Overflow encountered.
-    Overflow,
-}
+#[cfg(feature = "scale-codec")]
+use impl_codec::impl_uint_scale_codec;
 
-construct_uint! {
+#[cfg(feature = "impl-rlp")]
+use impl_rlp::impl_uint_rlp;
+
+construct_primitive_uint! {
     /// 128-bit unsigned integer.
     pub struct U128(2);
 }
-construct_uint! {
+construct_primitive_uint! {
     /// 256-bit unsigned integer.
     pub struct U256(4);
 }
-construct_uint! {
+construct_primitive_uint! {
     /// 512-bits unsigned integer.
     pub struct U512(8);
 }
+construct_uint! {
+    /// 1024-bits unsigned integer.
+    pub struct U1024(16);
+}
 
-construct_fixed_hash! {
+pub use checked::CheckedMath;
+#[cfg(feature = "scale-codec")]
+pub use compact::CompactUint;
+pub use convert::{SaturatingFrom, TruncatingFrom};
+pub use endian::{BigEndian, LittleEndian};
+pub use error::Error;
+pub use fixed::{Decimals, FixedU256, D12, D18, D6, D8, D9};
+#[cfg(feature = "scale-codec")]
+pub use impl_codec::{MaxEncodedLen, SliceCodec, SliceView};
+pub use int::{I128, I256, I512};
+pub use montgomery::MontgomeryContext;
+pub use mul_div::Rounding;
+pub use wrapping::{Saturating, Wrapping};
+
+construct_primitive_hash! {
     /// Fixed-size uninterpreted hash type with 20 bytes (160 bits) size.
     pub struct H160(20);
 }
-construct_fixed_hash! {
+construct_primitive_hash! {
     /// Fixed-size uninterpreted hash type with 32 bytes (256 bits) size.
     pub struct H256(32);
 }
-construct_fixed_hash! {
+construct_primitive_hash! {
     /// Fixed-size uninterpreted hash type with 64 bytes (512 bits) size.
     pub struct H512(64);
 }
@@ -78,157 +173,143 @@ construct_fixed_hash! {
 mod serde_impls {
     use super::*;
 
-    impl_uint_serde!(U128, 2);
-    impl_uint_serde!(U256, 4);
-    impl_uint_serde!(U512, 8);
-
-    impl_fixed_hash_serde!(H160, 20);
-    impl_fixed_hash_serde!(H256, 32);
-    impl_fixed_hash_serde!(H512, 64);
+    impl_uint_serde!(I128, 2);
+    impl_uint_serde!(I256, 4);
+    impl_uint_serde!(I512, 8);
 }
 
-#[cfg(feature = "impl-codec")]
+#[cfg(feature = "codec")]
 mod codec_impls {
     use super::*;
 
-    impl_uint_codec!(U128, 2);
-    impl_uint_codec!(U256, 4);
-    impl_uint_codec!(U512, 8);
-
-    impl_fixed_hash_codec!(H160, 20);
-    impl_fixed_hash_codec!(H256, 32);
-    impl_fixed_hash_codec!(H512, 64);
+    impl_uint_codec!(I128, 2);
+    impl_uint_codec!(I256, 4);
+    impl_uint_codec!(I512, 8);
 }
 
-#[cfg(feature = "impl-rlp")]
-mod rlp_impls {
+#[cfg(feature = "scale-codec")]
+mod scale_codec_impls {
     use super::*;
 
-    impl_uint_rlp!(U128, 2);
-    impl_uint_rlp!(U256, 4);
-    impl_uint_rlp!(U512, 8);
-
-    impl_fixed_hash_rlp!(H160, 20);
-    impl_fixed_hash_rlp!(H256, 32);
-    impl_fixed_hash_rlp!(H512, 64);
+    impl_uint_scale_codec!(I128, 2);
+    impl_uint_scale_codec!(I256, 4);
+    impl_uint_scale_codec!(I512, 8);
 }
 
-impl_fixed_hash_conversions!(H256, H160);
+// Invoked at the root, where `module_path!` gives the path of the public types.
+__primitive_scale_info!(uint, I128, 2);
+__primitive_scale_info!(uint, I256, 4);
+__primitive_scale_info!(uint, I512, 8);
 
-impl U256 {
-    /// Multiplies two 256-bit integers to produce full 512-bit integer
-    /// No overflow possible
-    #[inline(always)]
-    #[allow(clippy::cognitive_complexity)]
-    pub fn full_mul(self, other: U256) -> U512 {
-        U512(uint_full_mul_reg!(U256, 4, self, other))
-    }
-}
+#[cfg(feature = "impl-rlp")]
+mod rlp_impls {
+    use super::*;
 
-impl From<U256> for U512 {
-    fn from(value: U256) -> U512 {
-        let U256(ref arr) = value;
-        let mut ret = [0; 8];
-        ret[0] = arr[0];
-        ret[1] = arr[1];
-        ret[2] = arr[2];
-        ret[3] = arr[3];
-        U512(ret)
-    }
+    impl_uint_rlp!(I128, 2);
+    impl_uint_rlp!(I256, 4);
+    impl_uint_rlp!(I512, 8);
 }
 
-impl TryFrom<U256> for U128 {
-    type Error = Error;
+impl_fixed_hash_conversions!(H256, H160);
 
-    fn try_from(value: U256) -> Result<U128, Error> {
-        let U256(ref arr) = value;
-        if arr[2] | arr[3] != 0 {
-            return Err(Error::Overflow);
+macro_rules! impl_full_mul {
+    ($narrow:ident, $n_words:tt, $wide:ident) => {
+        impl $narrow {
+            /// Multiplies two integers to produce full integer of twice the width
+            /// No overflow possible
+            #[inline(always)]
+            #[allow(clippy::cognitive_complexity)]
+            pub fn full_mul(self, other: $narrow) -> $wide {
+                $wide(uint_full_mul_reg!($narrow, $n_words, self, other))
+            }
         }
-        let mut ret = [0; 2];
-        ret[0] = arr[0];
-        ret[1] = arr[1];
-        Ok(U128(ret))
-    }
-}
-
-impl TryFrom<U512> for U256 {
-    type Error = Error;
 
-    fn try_from(value: U512) -> Result<U256, Error> {
-        let U512(ref arr) = value;
-        if arr[4] | arr[5] | arr[6] | arr[7] != 0 {
-            return Err(Error::Overflow);
+        impl $wide {
+            /// Splits the integer into its `(high, low)` halves.
+            pub fn into_halves(self) -> ($narrow, $narrow) {
+                let $wide(ref arr) = self;
+                let mut hi = [0; $n_words];
+                let mut lo = [0; $n_words];
+                hi.copy_from_slice(&arr[$n_words..]);
+                lo.copy_from_slice(&arr[..$n_words]);
+                ($narrow(hi), $narrow(lo))
+            }
+
+            /// Joins the `(high, low)` halves into an integer of twice the width.
+            pub fn from_halves(hi: $narrow, lo: $narrow) -> $wide {
+                let mut ret = [0; $n_words * 2];
+                ret[$n_words..].copy_from_slice(&hi.0);
+                ret[..$n_words].copy_from_slice(&lo.0);
+                $wide(ret)
+            }
+
+            /// Returns a pair `(self / other, self % other)` of integers of half the width.
+            ///
+            /// Returns `Error::Overflow` if the quotient does not fit in half the width.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `other` is zero.
+            pub fn div_mod_narrow(self, other: $narrow) -> Result<($narrow, $narrow), Error> {
+                let (quotient, remainder) = self.div_mod($wide::from(other));
+                let (quotient_hi, quotient) = quotient.into_halves();
+                if !quotient_hi.is_zero() {
+                    return Err(Error::Overflow);
+                }
+                // the remainder is smaller than the divisor
+                Ok((quotient, remainder.into_halves().1))
+            }
         }
-        let mut ret = [0; 4];
-        ret[0] = arr[0];
-        ret[1] = arr[1];
-        ret[2] = arr[2];
-        ret[3] = arr[3];
-        Ok(U256(ret))
-    }
+    };
 }
 
-impl TryFrom<U512> for U128 {
-    type Error = Error;
-
-    fn try_from(value: U512) -> Result<U128, Error> {
-        let U512(ref arr) = value;
-        if arr[2] | arr[3] | arr[4] | arr[5] | arr[6] | arr[7] != 0 {
-            return Err(Error::Overflow);
+impl_full_mul!(U128, 2, U256);
+impl_full_mul!(U256, 4, U512);
+impl_full_mul!(U512, 8, U1024);
+
+macro_rules! impl_uint_widening {
+    ($narrow:ident, $n_narrow_words:tt, $wide:ident, $n_wide_words:tt) => {
+        impl From<$narrow> for $wide {
+            fn from(value: $narrow) -> $wide {
+                let mut ret = [0; $n_wide_words];
+                ret[..$n_narrow_words].copy_from_slice(&value.0);
+                $wide(ret)
+            }
         }
-        let mut ret = [0; 2];
-        ret[0] = arr[0];
-        ret[1] = arr[1];
-        Ok(U128(ret))
-    }
-}
-
-impl From<U128> for U512 {
-    fn from(value: U128) -> U512 {
-        let U128(ref arr) = value;
-        let mut ret = [0; 8];
-        ret[0] = arr[0];
-        ret[1] = arr[1];
-        U512(ret)
-    }
-}
 
-impl From<U128> for U256 {
-    fn from(value: U128) -> U256 {
-        let U128(ref arr) = value;
-        let mut ret = [0; 4];
-        ret[0] = arr[0];
-        ret[1] = arr[1];
-        U256(ret)
-    }
-}
+        impl<'a> From<&'a $narrow> for $wide {
+            fn from(value: &'a $narrow) -> $wide {
+                $wide::from(*value)
+            }
+        }
 
-impl<'a> From<&'a U256> for U512 {
-    fn from(value: &'a U256) -> U512 {
-        let U256(ref arr) = *value;
-        let mut ret = [0; 8];
-        ret[0] = arr[0];
-        ret[1] = arr[1];
-        ret[2] = arr[2];
-        ret[3] = arr[3];
-        U512(ret)
-    }
-}
+        impl TryFrom<$wide> for $narrow {
+            type Error = Error;
+
+            fn try_from(value: $wide) -> Result<$narrow, Error> {
+                let $wide(ref arr) = value;
+                if arr[$n_narrow_words..].iter().any(|word| *word != 0) {
+                    return Err(Error::Overflow);
+                }
+                let mut ret = [0; $n_narrow_words];
+                ret.copy_from_slice(&arr[..$n_narrow_words]);
+                Ok($narrow(ret))
+            }
+        }
 
-impl<'a> TryFrom<&'a U512> for U256 {
-    type Error = Error;
+        impl<'a> TryFrom<&'a $wide> for $narrow {
+            type Error = Error;
 
-    fn try_from(value: &'a U512) -> Result<U256, Error> {
-        let U512(ref arr) = *value;
-        if arr[4] | arr[5] | arr[6] | arr[7] != 0 {
-            return Err(Error::Overflow);
+            fn try_from(value: &'a $wide) -> Result<$narrow, Error> {
+                $narrow::try_from(*value)
+            }
         }
-        let mut ret = [0; 4];
-        ret[0] = arr[0];
-        ret[1] = arr[1];
-        ret[2] = arr[2];
-        ret[3] = arr[3];
-        Ok(U256(ret))
-    }
+    };
 }
+
+impl_uint_widening!(U128, 2, U256, 4);
+impl_uint_widening!(U128, 2, U512, 8);
+impl_uint_widening!(U256, 4, U512, 8);
+impl_uint_widening!(U128, 2, U1024, 16);
+impl_uint_widening!(U256, 4, U1024, 16);
+impl_uint_widening!(U512, 8, U1024, 16);
@@ -0,0 +1,123 @@
+// Copyright 2019 Chainpool
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Exact integer roots and logarithms of the uint types.
+
+use crate::{U1024, U128, U256, U512};
+
+macro_rules! impl_roots_and_logs {
+    ($name:ident) => {
+        impl $name {
+            /// Returns the square root, rounded down.
+            pub fn isqrt(self) -> $name {
+                if self.is_zero() {
+                    return self;
+                }
+                // Newton's method, starting from a power of two which is not below the root
+                let mut x = $name::one() << ((self.bits() + 1) / 2);
+                loop {
+                    let y = (x + self / x) >> 1;
+                    if y >= x {
+                        return x;
+                    }
+                    x = y;
+                }
+            }
+
+            /// Returns the `n`-th root, rounded down.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `n` is zero.
+            pub fn integer_nth_root(self, n: u32) -> $name {
+                assert!(n != 0, "zeroth root");
+                if n == 1 || self.is_zero() {
+                    return self;
+                }
+                // Newton's method, starting from a power of two which is not below the root
+                let n_usize = n as usize;
+                let mut x = $name::one() << ((self.bits() + n_usize - 1) / n_usize);
+                loop {
+                    // a power which does not fit is certainly larger than `self`
+                    let quotient = match x.checked_pow($name::from(n - 1)) {
+                        Some(power) => self / power,
+                        None => $name::zero(),
+                    };
+                    // a step which overflows would not decrease the estimate either
+                    let y = match (x * (n - 1)).checked_add(quotient) {
+                        Some(sum) => sum / n,
+                        None => return x,
+                    };
+                    if y >= x {
+                        return x;
+                    }
+                    x = y;
+                }
+            }
+
+            /// Returns the base 2 logarithm, rounded down.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `self` is zero.
+            pub fn log2_floor(&self) -> u32 {
+                assert!(!self.is_zero(), "logarithm of zero");
+                self.bits() as u32 - 1
+            }
+
+            /// Returns the base 2 logarithm, rounded up.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `self` is zero.
+            pub fn log2_ceil(&self) -> u32 {
+                let log = self.log2_floor();
+                if (*self & (*self - 1)).is_zero() {
+                    log
+                } else {
+                    log + 1
+                }
+            }
+
+            /// Returns the base 10 logarithm, rounded down.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `self` is zero.
+            pub fn log10(&self) -> u32 {
+                self.log_base($name::from(10))
+            }
+
+            /// Returns the logarithm with respect to an arbitrary base, rounded down.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `self` is zero or `base` is less than 2.
+            pub fn log_base(&self, base: $name) -> u32 {
+                assert!(!self.is_zero(), "logarithm of zero");
+                assert!(base > $name::one(), "logarithm base less than 2");
+                // `base^log` does not exceed `2^log2_floor(self)`, so the estimate is a lower bound
+                let mut log = self.log2_floor() / base.log2_ceil();
+                let mut power = base.pow($name::from(log));
+                while let Some(next) = power.checked_mul(base) {
+                    if next > *self {
+                        break;
+                    }
+                    power = next;
+                    log += 1;
+                }
+                log
+            }
+        }
+    };
+}
+
+impl_roots_and_logs!(U128);
+impl_roots_and_logs!(U256);
+impl_roots_and_logs!(U512);
+impl_roots_and_logs!(U1024);
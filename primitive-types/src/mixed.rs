@@ -0,0 +1,129 @@
+// Copyright 2019 Chainpool
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Arithmetic and comparisons between `U128`, `U256` and `U512`.
+//!
+//! The operators widen the narrower operand, so the result has the type of the
+//! wider one. `construct_uint!` already provides `Add`, `Sub`, `Div` and `Rem`
+//! with a narrower right operand; the rest is implemented here. The
+//! `narrowing_*` methods convert the result back to the narrower type.
+
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::ops::{Add, Div, Mul, Rem, Sub};
+
+use crate::{Error, U128, U256, U512};
+
+macro_rules! impl_widening_op {
+    ($trait:ident, $fn:ident, $narrow:ident, $wide:ident) => {
+        impl $trait<$wide> for $narrow {
+            type Output = $wide;
+
+            fn $fn(self, other: $wide) -> $wide {
+                $trait::$fn($wide::from(self), other)
+            }
+        }
+    };
+}
+
+macro_rules! impl_mixed_width {
+    ($narrow:ident, $wide:ident) => {
+        impl_widening_op!(Add, add, $narrow, $wide);
+        impl_widening_op!(Sub, sub, $narrow, $wide);
+        impl_widening_op!(Mul, mul, $narrow, $wide);
+        impl_widening_op!(Div, div, $narrow, $wide);
+        impl_widening_op!(Rem, rem, $narrow, $wide);
+
+        impl Mul<$narrow> for $wide {
+            type Output = $wide;
+
+            fn mul(self, other: $narrow) -> $wide {
+                self * $wide::from(other)
+            }
+        }
+
+        impl PartialEq<$wide> for $narrow {
+            fn eq(&self, other: &$wide) -> bool {
+                $wide::from(*self) == *other
+            }
+        }
+
+        impl PartialEq<$narrow> for $wide {
+            fn eq(&self, other: &$narrow) -> bool {
+                *self == $wide::from(*other)
+            }
+        }
+
+        impl PartialOrd<$wide> for $narrow {
+            fn partial_cmp(&self, other: &$wide) -> Option<Ordering> {
+                Some($wide::from(*self).cmp(other))
+            }
+        }
+
+        impl PartialOrd<$narrow> for $wide {
+            fn partial_cmp(&self, other: &$narrow) -> Option<Ordering> {
+                Some(self.cmp(&$wide::from(*other)))
+            }
+        }
+    };
+}
+
+impl_mixed_width!(U128, U256);
+impl_mixed_width!(U128, U512);
+impl_mixed_width!(U256, U512);
+
+macro_rules! impl_narrowing_ops {
+    ($name:ident) => {
+        impl $name {
+            /// Computes `self + other` at 512 bits and narrows the sum back.
+            ///
+            /// Returns `Error::Overflow` if the sum does not fit.
+            pub fn narrowing_add<T: Into<U512>>(self, other: T) -> Result<$name, Error> {
+                let sum = U512::from(self).checked_add(other.into());
+                $name::try_from(sum.ok_or(Error::Overflow)?)
+            }
+
+            /// Computes `self - other` at 512 bits and narrows the difference back.
+            ///
+            /// Returns `Error::Underflow` if the difference is negative.
+            pub fn narrowing_sub<T: Into<U512>>(self, other: T) -> Result<$name, Error> {
+                let difference = U512::from(self).checked_sub(other.into());
+                $name::try_from(difference.ok_or(Error::Underflow)?)
+            }
+
+            /// Computes `self * other` at 512 bits and narrows the product back.
+            ///
+            /// Returns `Error::Overflow` if the product does not fit.
+            pub fn narrowing_mul<T: Into<U512>>(self, other: T) -> Result<$name, Error> {
+                let product = U512::from(self).checked_mul(other.into());
+                $name::try_from(product.ok_or(Error::Overflow)?)
+            }
+
+            /// Computes `self / other` and narrows the quotient back, which always fits.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `other` is zero.
+            pub fn narrowing_div<T: Into<U512>>(self, other: T) -> Result<$name, Error> {
+                $name::try_from(U512::from(self) / other.into())
+            }
+
+            /// Computes `self % other` and narrows the remainder back, which always fits.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `other` is zero.
+            pub fn narrowing_rem<T: Into<U512>>(self, other: T) -> Result<$name, Error> {
+                $name::try_from(U512::from(self) % other.into())
+            }
+        }
+    };
+}
+
+impl_narrowing_ops!(U128);
+impl_narrowing_ops!(U256);
@@ -0,0 +1,110 @@
+// Copyright 2019 Chainpool
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Modular arithmetic following the EVM `ADDMOD`/`MULMOD` conventions: the
+//! intermediate results never wrap and a zero modulus yields zero.
+
+use core::convert::TryFrom;
+
+use crate::{U1024, U256, U512};
+
+macro_rules! impl_mod_arith {
+    ($name:ident, $wide:ident) => {
+        impl $name {
+            /// Computes `(self + other) % modulus` without the sum wrapping around.
+            ///
+            /// Returns zero if `modulus` is zero.
+            pub fn add_mod(self, other: $name, modulus: $name) -> $name {
+                if modulus.is_zero() {
+                    return modulus;
+                }
+                let (sum, overflow) = (self % modulus).overflowing_add(other % modulus);
+                // the exact sum is below `2 * modulus`, one subtraction is enough
+                if overflow || sum >= modulus {
+                    sum.overflowing_sub(modulus).0
+                } else {
+                    sum
+                }
+            }
+
+            /// Computes `(self - other) mod modulus`, the result always lying in `[0, modulus)`.
+            ///
+            /// Returns zero if `modulus` is zero.
+            pub fn sub_mod(self, other: $name, modulus: $name) -> $name {
+                if modulus.is_zero() {
+                    return modulus;
+                }
+                let (a, b) = (self % modulus, other % modulus);
+                if a >= b {
+                    a - b
+                } else {
+                    modulus - (b - a)
+                }
+            }
+
+            /// Computes `(self * other) % modulus` on the full width product, so it never
+            /// overflows.
+            ///
+            /// Returns zero if `modulus` is zero.
+            pub fn mul_mod(self, other: $name, modulus: $name) -> $name {
+                if modulus.is_zero() {
+                    return modulus;
+                }
+                let remainder = self.full_mul(other) % $wide::from(modulus);
+                $name::try_from(remainder).expect("remainder is less than the modulus; qed")
+            }
+
+            /// Computes `self.pow(exp) % modulus` by square-and-multiply.
+            ///
+            /// Returns zero if `modulus` is zero.
+            pub fn pow_mod(self, exp: $name, modulus: $name) -> $name {
+                if modulus.is_zero() {
+                    return modulus;
+                }
+                let mut base = self % modulus;
+                let mut result = $name::one() % modulus;
+                for i in 0..exp.bits() {
+                    if exp.bit(i) {
+                        result = result.mul_mod(base, modulus);
+                    }
+                    base = base.mul_mod(base, modulus);
+                }
+                result
+            }
+
+            /// Computes the inverse of `self` modulo `modulus`, that is the `x` in
+            /// `[0, modulus)` for which `self * x % modulus == 1 % modulus`.
+            ///
+            /// Returns `None` if `self` and `modulus` are not coprime or `modulus` is zero.
+            pub fn inv_mod(self, modulus: $name) -> Option<$name> {
+                if modulus.is_zero() {
+                    return None;
+                }
+                // extended Euclid, keeping the Bezout coefficient of `self` modulo `modulus`
+                let (mut r0, mut r1) = (modulus, self % modulus);
+                let (mut t0, mut t1) = ($name::zero(), $name::one());
+                while !r1.is_zero() {
+                    let (quotient, remainder) = r0.div_mod(r1);
+                    let t = t0.sub_mod(quotient.mul_mod(t1, modulus), modulus);
+                    t0 = t1;
+                    t1 = t;
+                    r0 = r1;
+                    r1 = remainder;
+                }
+                if r0 == $name::one() {
+                    Some(t0 % modulus)
+                } else {
+                    None
+                }
+            }
+        }
+    };
+}
+
+impl_mod_arith!(U256, U512);
+impl_mod_arith!(U512, U1024);
@@ -0,0 +1,152 @@
+// Copyright 2019 Chainpool
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Montgomery multiplication modulo a fixed odd modulus, working directly on
+//! the 64-bit limbs of the uint types.
+
+use core::convert::TryFrom;
+
+use crate::{U1024, U256, U512};
+
+/// Precomputed constants for arithmetic modulo a fixed odd modulus in Montgomery form.
+///
+/// A value `x` is represented by `x * R mod modulus`, where `R` is `2^BITS` of the
+/// underlying type. Multiplication of such representations only needs word
+/// multiplications and shifts instead of a wide division.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MontgomeryContext<T> {
+    modulus: T,
+    /// `-modulus^-1 mod 2^64`.
+    inv: u64,
+    /// `R mod modulus`, the representation of one.
+    one: T,
+    /// `R^2 mod modulus`, used to convert into Montgomery form.
+    r2: T,
+}
+
+/// Returns the low and high words of `a + b * c + carry`, which can not overflow 128 bits.
+#[inline(always)]
+fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let wide = u128::from(a) + u128::from(b) * u128::from(c) + u128::from(carry);
+    (wide as u64, (wide >> 64) as u64)
+}
+
+macro_rules! impl_montgomery {
+    ($name:ident, $n_words:tt, $wide:ident) => {
+        impl MontgomeryContext<$name> {
+            /// Creates the context for the given modulus.
+            ///
+            /// Returns `None` if the modulus is even or one.
+            pub fn new(modulus: $name) -> Option<Self> {
+                if !modulus.bit(0) || modulus == $name::one() {
+                    return None;
+                }
+                // Newton's iteration doubles the number of correct low bits of the inverse
+                let low = modulus.0[0];
+                let mut inv = 1u64;
+                for _ in 0..6 {
+                    inv = inv.wrapping_mul(2u64.wrapping_sub(low.wrapping_mul(inv)));
+                }
+                let r = ($wide::one() << ($n_words * 64)) % $wide::from(modulus);
+                let one = $name::try_from(r).expect("remainder is less than the modulus; qed");
+                Some(MontgomeryContext {
+                    modulus,
+                    inv: inv.wrapping_neg(),
+                    one,
+                    r2: one.mul_mod(one, modulus),
+                })
+            }
+
+            /// Returns the modulus.
+            pub fn modulus(&self) -> $name {
+                self.modulus
+            }
+
+            /// Returns the Montgomery form of one.
+            pub fn one(&self) -> $name {
+                self.one
+            }
+
+            /// Converts `x` into Montgomery form, reducing it modulo the modulus first.
+            pub fn to_montgomery(&self, x: $name) -> $name {
+                self.mul(x % self.modulus, self.r2)
+            }
+
+            /// Converts `x` out of Montgomery form.
+            pub fn from_montgomery(&self, x: $name) -> $name {
+                self.mul(x, $name::one())
+            }
+
+            /// Multiplies two values in Montgomery form.
+            ///
+            /// Both operands must be less than the modulus.
+            pub fn mul(&self, a: $name, b: $name) -> $name {
+                let (a, b, m) = (&a.0, &b.0, &self.modulus.0);
+                let mut t = [0u64; $n_words + 2];
+                for i in 0..$n_words {
+                    // t += a * b[i]
+                    let mut carry = 0;
+                    for j in 0..$n_words {
+                        let (low, high) = mac(t[j], a[j], b[i], carry);
+                        t[j] = low;
+                        carry = high;
+                    }
+                    let (sum, overflow) = t[$n_words].overflowing_add(carry);
+                    t[$n_words] = sum;
+                    t[$n_words + 1] = overflow as u64;
+
+                    // t = (t + k * modulus) / 2^64, with k chosen so the division is exact
+                    let k = t[0].wrapping_mul(self.inv);
+                    let (_, mut carry) = mac(t[0], k, m[0], 0);
+                    for j in 1..$n_words {
+                        let (low, high) = mac(t[j], k, m[j], carry);
+                        t[j - 1] = low;
+                        carry = high;
+                    }
+                    let (sum, overflow) = t[$n_words].overflowing_add(carry);
+                    t[$n_words - 1] = sum;
+                    t[$n_words] = t[$n_words + 1] + overflow as u64;
+                }
+
+                // the result is below twice the modulus
+                let mut result = [0u64; $n_words];
+                result.copy_from_slice(&t[..$n_words]);
+                let result = $name(result);
+                if t[$n_words] != 0 || result >= self.modulus {
+                    result.overflowing_sub(self.modulus).0
+                } else {
+                    result
+                }
+            }
+
+            /// Squares a value in Montgomery form.
+            ///
+            /// The operand must be less than the modulus.
+            pub fn square(&self, a: $name) -> $name {
+                self.mul(a, a)
+            }
+
+            /// Raises a value in Montgomery form to the power `exp`.
+            ///
+            /// The base must be less than the modulus.
+            pub fn pow(&self, base: $name, exp: $name) -> $name {
+                let mut result = self.one;
+                for i in (0..exp.bits()).rev() {
+                    result = self.square(result);
+                    if exp.bit(i) {
+                        result = self.mul(result, base);
+                    }
+                }
+                result
+            }
+        }
+    };
+}
+
+impl_montgomery!(U256, 4, U512);
+impl_montgomery!(U512, 8, U1024);
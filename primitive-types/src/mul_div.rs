@@ -0,0 +1,117 @@
+// Copyright 2019 Chainpool
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! `a * b / c` with explicit rounding, computed on an intermediate twice as
+//! wide as the operands so the product never overflows.
+
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+
+use crate::{Error, U1024, U128, U256, U512};
+
+/// Rounding direction for operations whose exact result is not representable.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    /// Round towards zero.
+    Floor,
+    /// Round away from zero.
+    Ceil,
+    /// Round to the nearest value, ties away from zero.
+    HalfUp,
+    /// Round to the nearest value, ties to the even value.
+    HalfEven,
+}
+
+macro_rules! impl_mul_div {
+    ($name:ident, $wide:ident) => {
+        impl $name {
+            /// Computes `self * mul / div` rounding as requested, without overflowing on
+            /// the intermediate product.
+            ///
+            /// Returns `Error::Overflow` if the result does not fit in the type.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `div` is zero.
+            pub fn mul_div(
+                self,
+                mul: $name,
+                div: $name,
+                rounding: Rounding,
+            ) -> Result<$name, Error> {
+                let divisor = $wide::from(div);
+                let (quotient, remainder) = self.full_mul(mul).div_mod(divisor);
+                let round_up = match rounding {
+                    Rounding::Floor => false,
+                    Rounding::Ceil => !remainder.is_zero(),
+                    // the remainder is smaller than the divisor, doubling it can not overflow
+                    Rounding::HalfUp => remainder << 1 >= divisor,
+                    Rounding::HalfEven => match (remainder << 1).cmp(&divisor) {
+                        Ordering::Less => false,
+                        Ordering::Equal => quotient.bit(0),
+                        Ordering::Greater => true,
+                    },
+                };
+                let quotient = $name::try_from(quotient)?;
+                if round_up {
+                    quotient.checked_add($name::one()).ok_or(Error::Overflow)
+                } else {
+                    Ok(quotient)
+                }
+            }
+
+            /// Computes `self * mul / div` rounding towards zero.
+            ///
+            /// Returns `Error::Overflow` if the result does not fit in the type.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `div` is zero.
+            pub fn mul_div_floor(self, mul: $name, div: $name) -> Result<$name, Error> {
+                self.mul_div(mul, div, Rounding::Floor)
+            }
+
+            /// Computes `self * mul / div` rounding away from zero.
+            ///
+            /// Returns `Error::Overflow` if the result does not fit in the type.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `div` is zero.
+            pub fn mul_div_ceil(self, mul: $name, div: $name) -> Result<$name, Error> {
+                self.mul_div(mul, div, Rounding::Ceil)
+            }
+
+            /// Computes `self * mul / div` rounding to the nearest value, ties away from zero.
+            ///
+            /// Returns `Error::Overflow` if the result does not fit in the type.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `div` is zero.
+            pub fn mul_div_round(self, mul: $name, div: $name) -> Result<$name, Error> {
+                self.mul_div(mul, div, Rounding::HalfUp)
+            }
+
+            /// Computes `self * mul / div` rounding to the nearest value, ties to even.
+            ///
+            /// Returns `Error::Overflow` if the result does not fit in the type.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `div` is zero.
+            pub fn mul_div_round_half_even(self, mul: $name, div: $name) -> Result<$name, Error> {
+                self.mul_div(mul, div, Rounding::HalfEven)
+            }
+        }
+    };
+}
+
+impl_mul_div!(U128, U256);
+impl_mul_div!(U256, U512);
+impl_mul_div!(U512, U1024);
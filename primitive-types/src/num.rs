@@ -0,0 +1,269 @@
+// Copyright 2019 Chainpool
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! `num-traits` implementations for the uint types.
+//!
+//! `from_str_radix` accepts radixes `2..=36` without a prefix. It reports an
+//! invalid digit as `Error::InvalidHexCharacter` with its byte position for
+//! radix 16 and as `Error::InvalidDecimalDigit` otherwise, and a value which
+//! does not fit as `Error::Overflow`.
+//!
+//! The hash types do not implement `Zero`: it requires `Add`, which hashes do
+//! not have. Use their inherent `zero` and `is_zero` instead.
+
+use num_traits::{
+    pow::Pow, Bounded, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, FromPrimitive, Num, One,
+    ToPrimitive, Unsigned, WrappingAdd, WrappingMul, WrappingSub, Zero,
+};
+
+use crate::{Error, U128, U256, U512};
+
+/// Returns the 64 most significant bits of the little-endian `words` and the
+/// number of bits below them. Set bits below are folded into the lowest returned
+/// bit, so converting the bits to a float rounds like converting the full value.
+fn significand(words: &[u64]) -> (u64, u32) {
+    let top = match words.iter().rposition(|w| *w != 0) {
+        Some(0) | None => return (words[0], 0),
+        Some(top) => top,
+    };
+    let zeros = words[top].leading_zeros();
+    let below = words[top - 1];
+    let bits = words[top] << zeros | below.checked_shr(64 - zeros).unwrap_or(0);
+    let sticky = below << zeros != 0 || words[..top - 1].iter().any(|w| *w != 0);
+    (bits | sticky as u64, top as u32 * 64 - zeros)
+}
+
+fn invalid_digit(radix: u32, position: usize) -> Error {
+    if radix == 16 {
+        Error::InvalidHexCharacter(position)
+    } else {
+        Error::InvalidDecimalDigit
+    }
+}
+
+macro_rules! impl_num_traits {
+    ($name:ident) => {
+        impl Zero for $name {
+            #[inline]
+            fn zero() -> Self {
+                $name::zero()
+            }
+
+            #[inline]
+            fn is_zero(&self) -> bool {
+                $name::is_zero(self)
+            }
+        }
+
+        impl One for $name {
+            #[inline]
+            fn one() -> Self {
+                $name::one()
+            }
+        }
+
+        impl Num for $name {
+            type FromStrRadixErr = Error;
+
+            /// # Panics
+            ///
+            /// Panics if `radix` is not in the range `2..=36`.
+            fn from_str_radix(s: &str, radix: u32) -> Result<Self, Error> {
+                assert!(
+                    radix >= 2 && radix <= 36,
+                    "radix must be in the range 2..=36"
+                );
+                if s.is_empty() {
+                    return Err(invalid_digit(radix, 0));
+                }
+                let radix_value = $name::from(radix);
+                s.char_indices()
+                    .try_fold($name::zero(), |acc, (position, c)| {
+                        let digit = c
+                            .to_digit(radix)
+                            .ok_or_else(|| invalid_digit(radix, position))?;
+                        acc.checked_mul(radix_value)
+                            .and_then(|acc| acc.checked_add($name::from(digit)))
+                            .ok_or(Error::Overflow)
+                    })
+            }
+        }
+
+        impl Bounded for $name {
+            #[inline]
+            fn min_value() -> Self {
+                $name::zero()
+            }
+
+            #[inline]
+            fn max_value() -> Self {
+                $name::max_value()
+            }
+        }
+
+        impl Unsigned for $name {}
+
+        impl CheckedAdd for $name {
+            #[inline]
+            fn checked_add(&self, v: &Self) -> Option<Self> {
+                $name::checked_add(*self, *v)
+            }
+        }
+
+        impl CheckedSub for $name {
+            #[inline]
+            fn checked_sub(&self, v: &Self) -> Option<Self> {
+                $name::checked_sub(*self, *v)
+            }
+        }
+
+        impl CheckedMul for $name {
+            #[inline]
+            fn checked_mul(&self, v: &Self) -> Option<Self> {
+                $name::checked_mul(*self, *v)
+            }
+        }
+
+        impl CheckedDiv for $name {
+            #[inline]
+            fn checked_div(&self, v: &Self) -> Option<Self> {
+                $name::checked_div(*self, *v)
+            }
+        }
+
+        impl num_traits::Saturating for $name {
+            #[inline]
+            fn saturating_add(self, v: Self) -> Self {
+                $name::saturating_add(self, v)
+            }
+
+            #[inline]
+            fn saturating_sub(self, v: Self) -> Self {
+                $name::saturating_sub(self, v)
+            }
+        }
+
+        impl WrappingAdd for $name {
+            #[inline]
+            fn wrapping_add(&self, v: &Self) -> Self {
+                self.overflowing_add(*v).0
+            }
+        }
+
+        impl WrappingSub for $name {
+            #[inline]
+            fn wrapping_sub(&self, v: &Self) -> Self {
+                self.overflowing_sub(*v).0
+            }
+        }
+
+        impl WrappingMul for $name {
+            #[inline]
+            fn wrapping_mul(&self, v: &Self) -> Self {
+                self.overflowing_mul(*v).0
+            }
+        }
+
+        impl ToPrimitive for $name {
+            #[inline]
+            fn to_i64(&self) -> Option<i64> {
+                self.to_u64().and_then(|value| value.to_i64())
+            }
+
+            #[inline]
+            fn to_u64(&self) -> Option<u64> {
+                if self.bits() > 64 {
+                    None
+                } else {
+                    Some(self.low_u64())
+                }
+            }
+
+            #[inline]
+            fn to_i128(&self) -> Option<i128> {
+                self.try_to_i128().ok()
+            }
+
+            #[inline]
+            fn to_u128(&self) -> Option<u128> {
+                self.try_to_u128().ok()
+            }
+
+            /// Rounds to the nearest value, ties to even, and to infinity above
+            /// `f32::MAX`, so it never fails.
+            fn to_f32(&self) -> Option<f32> {
+                let (bits, exp) = significand(&self.0);
+                let mut value = bits as f32;
+                for _ in 0..exp / 64 {
+                    value *= 18_446_744_073_709_551_616.0;
+                }
+                Some(value * (1u64 << (exp % 64)) as f32)
+            }
+
+            /// Rounds to the nearest value, ties to even, so it never fails.
+            fn to_f64(&self) -> Option<f64> {
+                let (bits, exp) = significand(&self.0);
+                let mut value = bits as f64;
+                for _ in 0..exp / 64 {
+                    value *= 18_446_744_073_709_551_616.0;
+                }
+                Some(value * (1u64 << (exp % 64)) as f64)
+            }
+        }
+
+        impl FromPrimitive for $name {
+            #[inline]
+            fn from_i64(n: i64) -> Option<Self> {
+                $name::try_from_i128(n.into()).ok()
+            }
+
+            #[inline]
+            fn from_u64(n: u64) -> Option<Self> {
+                Some($name::from(n))
+            }
+
+            #[inline]
+            fn from_i128(n: i128) -> Option<Self> {
+                $name::try_from_i128(n).ok()
+            }
+
+            #[inline]
+            fn from_u128(n: u128) -> Option<Self> {
+                Some($name::from(n))
+            }
+        }
+
+        impl Pow<u32> for $name {
+            type Output = $name;
+
+            /// # Panics
+            ///
+            /// Panics if the result overflows, like the inherent `pow`.
+            #[inline]
+            fn pow(self, rhs: u32) -> $name {
+                $name::pow(self, $name::from(rhs))
+            }
+        }
+
+        impl Pow<$name> for $name {
+            type Output = $name;
+
+            /// # Panics
+            ///
+            /// Panics if the result overflows, like the inherent `pow`.
+            #[inline]
+            fn pow(self, rhs: $name) -> $name {
+                $name::pow(self, rhs)
+            }
+        }
+    };
+}
+
+impl_num_traits!(U128);
+impl_num_traits!(U256);
+impl_num_traits!(U512);
@@ -19,7 +19,7 @@ mod serde_tests {
 
     use impl_serde::serde::de::DeserializeOwned;
 
-    use crate::{H160, H256, H512, U128, U256, U512};
+    use crate::{H160, H256, H512, I128, I256, I512, U128, U256, U512};
 
     fn ser_from_str_err_is_data<T: DeserializeOwned + Debug>(s: &str) -> bool {
         serde_json::from_str::<T>(s).unwrap_err().is_data()
@@ -49,7 +49,7 @@ mod serde_tests {
                     );
                     assert_eq!(
                         number,
-                        serde_json::from_str(&format!("{:?}", expected)).unwrap()
+                        serde_json::from_str::<$name>(&format!("{:?}", expected)).unwrap()
                     );
                 }
 
@@ -66,6 +66,9 @@ mod serde_tests {
     test_serde_uint!(U128, test_serde_u128);
     test_serde_uint!(U256, test_serde_u256);
     test_serde_uint!(U512, test_serde_u512);
+    test_serde_uint!(I128, test_serde_i128);
+    test_serde_uint!(I256, test_serde_i256);
+    test_serde_uint!(I512, test_serde_i512);
 
     #[test]
     fn test_serde_uint_large_values() {
@@ -78,6 +81,25 @@ mod serde_tests {
         ));
     }
 
+    #[test]
+    fn test_serde_fixed() {
+        use crate::{FixedU256, D18};
+
+        let value = FixedU256::<D18>::from_inner(U256::from(1_000));
+        assert_eq!(serde_json::to_string(&value).unwrap(), r#""0x3e8""#);
+        assert_eq!(
+            serde_json::from_str::<FixedU256<D18>>(r#""0x3e8""#).unwrap(),
+            value
+        );
+    }
+
+    #[test]
+    fn test_serde_negative_int() {
+        let json = r#""0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff""#;
+        assert_eq!(serde_json::to_string(&I256::from(-1)).unwrap(), json);
+        assert_eq!(serde_json::from_str::<I256>(json).unwrap(), I256::from(-1));
+    }
+
     macro_rules! test_serde_hash {
         ($name: ident, $test_name: ident) => {
             #[test]
@@ -137,6 +159,103 @@ mod serde_tests {
              ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff\"",
         ));
     }
+    #[test]
+    fn test_serde_wrapping() {
+        use crate::{Saturating, Wrapping};
+
+        let value = U256::from(0x0102);
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(serde_json::to_string(&Wrapping(value)).unwrap(), json);
+        assert_eq!(serde_json::to_string(&Saturating(value)).unwrap(), json);
+        assert_eq!(
+            serde_json::from_str::<Wrapping<U256>>(&json).unwrap(),
+            Wrapping(value)
+        );
+        assert_eq!(
+            serde_json::from_str::<Saturating<U256>>(&json).unwrap(),
+            Saturating(value)
+        );
+        assert!(ser_from_str_err_is_data::<Wrapping<U128>>(
+            r#""0x1ffffffffffffffffffffffffffffffff""#
+        ));
+    }
+
+    #[test]
+    fn test_serde_endian() {
+        use crate::{BigEndian, LittleEndian};
+
+        let value = U128::from(0x0102);
+        let be = r#""0x00000000000000000000000000000102""#;
+        let le = r#""0x02010000000000000000000000000000""#;
+        assert_eq!(serde_json::to_string(&BigEndian(value)).unwrap(), be);
+        assert_eq!(serde_json::to_string(&LittleEndian(value)).unwrap(), le);
+        assert_eq!(
+            serde_json::from_str::<BigEndian<U128>>(be).unwrap(),
+            BigEndian(value)
+        );
+        assert_eq!(
+            serde_json::from_str::<LittleEndian<U128>>(le).unwrap(),
+            LittleEndian(value)
+        );
+        assert!(ser_from_str_err_is_data::<BigEndian<U128>>(r#""0x0102""#));
+
+        let value = U512::max_value() - 1;
+        let json = serde_json::to_string(&LittleEndian(value)).unwrap();
+        assert!(json.starts_with(r#""0xfeff"#));
+        assert_eq!(
+            serde_json::from_str::<LittleEndian<U512>>(&json).unwrap(),
+            LittleEndian(value)
+        );
+        let json = serde_json::to_string(&BigEndian(U256::one())).unwrap();
+        assert_eq!(json.len(), 2 + 2 + 64);
+        assert_eq!(
+            serde_json::from_str::<BigEndian<U256>>(&json).unwrap(),
+            BigEndian(U256::one())
+        );
+    }
+
+    #[test]
+    fn test_serde_error() {
+        use crate::Error;
+
+        let tests = vec![
+            (Error::Overflow, r#""Overflow""#),
+            (Error::Underflow, r#""Underflow""#),
+            (Error::Negative, r#""Negative""#),
+            (
+                Error::InvalidLength {
+                    expected: 32,
+                    actual: 20,
+                },
+                r#"{"InvalidLength":{"expected":32,"actual":20}}"#,
+            ),
+            (
+                Error::InvalidHexCharacter(7),
+                r#"{"InvalidHexCharacter":7}"#,
+            ),
+            (Error::InvalidDecimalDigit, r#""InvalidDecimalDigit""#),
+            (Error::DivisionByZero, r#""DivisionByZero""#),
+        ];
+        for (error, expected) in tests {
+            assert_eq!(serde_json::to_string(&error).unwrap(), expected);
+            assert_eq!(serde_json::from_str::<Error>(expected).unwrap(), error);
+        }
+        assert_eq!(
+            serde_json::from_str::<Error>(r#"{"InvalidLength":{"actual":20,"expected":32}}"#)
+                .unwrap(),
+            Error::InvalidLength {
+                expected: 32,
+                actual: 20
+            }
+        );
+        assert!(ser_from_str_err_is_data::<Error>(r#""Unknown""#));
+        assert!(ser_from_str_err_is_data::<Error>(
+            r#"{"InvalidLength":{"expected":32}}"#
+        ));
+        assert!(ser_from_str_err_is_data::<Error>(
+            r#"{"InvalidLength":{"expected":32,"actual":20,"other":1}}"#
+        ));
+    }
 }
 
 #[cfg(feature = "codec")]
@@ -147,7 +266,7 @@ mod codec_tests {
     use impl_codec::codec::{Decode, Encode};
 
     use super::helper;
-    use crate::{H160, H256, H512, U128, U256, U512};
+    use crate::{H160, H256, H512, I128, I256, I512, U128, U256, U512};
     fn convert_hex_str_endian(value: &str) -> Vec<u8> {
         helper::from_hex_str(value).into_iter().rev().collect()
     }
@@ -182,6 +301,29 @@ mod codec_tests {
     test_codec_uint!(U128, 2, test_codec_u128);
     test_codec_uint!(U256, 4, test_codec_u256);
     test_codec_uint!(U512, 8, test_codec_u512);
+    test_codec_uint!(I128, 2, test_codec_i128);
+    test_codec_uint!(I256, 4, test_codec_i256);
+    test_codec_uint!(I512, 8, test_codec_i512);
+
+    #[test]
+    fn test_codec_fixed() {
+        use crate::{FixedU256, D18};
+
+        let value = FixedU256::<D18>::from_inner(U256::from(1_000));
+        assert_eq!(value.encode(), U256::from(1_000).encode());
+        let decoded: FixedU256<D18> = Decode::decode(&mut value.encode().as_slice()).unwrap();
+        assert_eq!(decoded, value);
+    }
+
+    #[test]
+    fn test_codec_negative_int() {
+        let value = I256::from(-2);
+        let encoded = value.encode();
+        let mut expected = vec![0xff; 32];
+        expected[0] = 0xfe;
+        assert_eq!(encoded, expected);
+        assert_eq!(I256::decode(&mut encoded.as_slice()), Some(value));
+    }
 
     macro_rules! test_codec_hash {
         ($name: ident, $test_name: ident) => {
@@ -216,239 +358,2486 @@ mod codec_tests {
     test_codec_hash!(H160, test_codec_h160);
     test_codec_hash!(H256, test_codec_h256);
     test_codec_hash!(H512, test_codec_h512);
+
+    #[test]
+    fn test_codec_wrapping() {
+        use crate::{Saturating, Wrapping};
+
+        let value = U512::max_value() - 1;
+        assert_eq!(Wrapping(value).encode(), value.encode());
+        assert_eq!(Saturating(value).encode(), value.encode());
+        assert_eq!(
+            Wrapping::<U512>::decode(&mut value.encode().as_slice()),
+            Some(Wrapping(value))
+        );
+        assert_eq!(
+            Saturating::<U512>::decode(&mut value.encode().as_slice()),
+            Some(Saturating(value))
+        );
+        assert_eq!(Wrapping::<U512>::decode(&mut &[0u8; 63][..]), None);
+    }
+
+    #[test]
+    fn test_codec_endian() {
+        use crate::{BigEndian, LittleEndian};
+
+        let value = U256::from(0x0102);
+        let mut be = vec![0u8; 32];
+        be[30] = 1;
+        be[31] = 2;
+        let mut le = vec![0u8; 32];
+        le[0] = 2;
+        le[1] = 1;
+        assert_eq!(BigEndian(value).encode(), be);
+        assert_eq!(LittleEndian(value).encode(), le);
+        assert_eq!(value.encode(), le);
+        assert_eq!(
+            BigEndian::<U256>::decode(&mut be.as_slice()),
+            Some(BigEndian(value))
+        );
+        assert_eq!(
+            LittleEndian::<U256>::decode(&mut le.as_slice()),
+            Some(LittleEndian(value))
+        );
+        assert_eq!(BigEndian::<U256>::decode(&mut &be[1..]), None);
+
+        let value = U128::max_value() - 1;
+        assert_eq!(
+            BigEndian::<U128>::decode(&mut BigEndian(value).encode().as_slice()),
+            Some(BigEndian(value))
+        );
+        let value = U512::max_value() - 1;
+        assert_eq!(
+            LittleEndian::<U512>::decode(&mut LittleEndian(value).encode().as_slice()),
+            Some(LittleEndian(value))
+        );
+    }
+
+    #[test]
+    fn test_codec_fixed_array() {
+        #[derive(Debug, PartialEq)]
+        struct Limbs([u32; 3]);
+        impl_codec::impl_fixed_array_codec!(Limbs, u32, 3);
+
+        #[derive(Debug, PartialEq)]
+        struct Deltas([i16; 2]);
+        impl_codec::impl_fixed_array_codec!(Deltas, i16, 2);
+
+        // The bytes are fixed, whatever the byte order of the host.
+        let limbs = Limbs([0x0102_0304, 0, u32::max_value()]);
+        let encoded = vec![4, 3, 2, 1, 0, 0, 0, 0, 0xff, 0xff, 0xff, 0xff];
+        assert_eq!(limbs.encode(), encoded);
+        assert_eq!(Limbs::decode(&mut encoded.as_slice()), Some(limbs));
+        assert_eq!(Limbs::decode(&mut &encoded[1..]), None);
+
+        let deltas = Deltas([-2, 0x0102]);
+        let encoded = vec![0xfe, 0xff, 2, 1];
+        assert_eq!(deltas.encode(), encoded);
+        assert_eq!(Deltas::decode(&mut encoded.as_slice()), Some(deltas));
+    }
+
+    #[test]
+    fn test_codec_error() {
+        use crate::Error;
+
+        let tests = vec![
+            (Error::Overflow, vec![0]),
+            (Error::Underflow, vec![1]),
+            (Error::Negative, vec![2]),
+            (
+                Error::InvalidLength {
+                    expected: 32,
+                    actual: 20,
+                },
+                vec![3, 32, 0, 0, 0, 0, 0, 0, 0, 20, 0, 0, 0, 0, 0, 0, 0],
+            ),
+            (
+                Error::InvalidHexCharacter(7),
+                vec![4, 7, 0, 0, 0, 0, 0, 0, 0],
+            ),
+            (Error::InvalidDecimalDigit, vec![5]),
+            (Error::DivisionByZero, vec![6]),
+        ];
+        for (error, encoded) in tests {
+            assert_eq!(error.encode(), encoded);
+            assert_eq!(Error::decode(&mut encoded.as_slice()), Some(error));
+        }
+        assert_eq!(Error::decode(&mut &[7u8][..]), None);
+        assert_eq!(Error::decode(&mut &[4u8, 7][..]), None);
+    }
 }
 
-#[cfg(feature = "rlp")]
-mod rlp_tests {
+#[cfg(feature = "scale-codec")]
+mod scale_codec_tests {
     #[cfg(not(feature = "std"))]
     use alloc::{vec, vec::Vec};
-    use core::{cmp, fmt};
 
-    use impl_rlp::rlp;
+    use impl_codec::scale_codec::{self, Decode, Encode, Input};
 
-    use super::helper;
-    use crate::{H160, U256};
+    use crate::{Error, H160, H256, H512, I128, I256, I512, U128, U256, U512};
 
-    struct ETestPair<T>(T, Vec<u8>);
+    macro_rules! test_scale_codec_uint {
+        ($name: ident, $size: expr, $test_name: ident) => {
+            #[test]
+            fn $test_name() {
+                for value in vec![
+                    $name::zero(),
+                    $name::from(0x0102),
+                    $name::from(u64::max_value()),
+                    $name::max_value() - 1,
+                ] {
+                    let mut expected = vec![0u8; $size * 8];
+                    value.to_little_endian(&mut expected);
+                    assert_eq!(value.encode(), expected);
+                    assert_eq!(value.size_hint(), $size * 8);
+                    assert_eq!($name::decode(&mut expected.as_slice()), Ok(value));
+                    assert!($name::decode(&mut &expected[1..]).is_err());
+                }
+            }
+        };
+    }
 
-    impl<T: rlp::Encodable> ETestPair<T> {
-        pub fn run_encode_test(self) {
-            let res = rlp::encode(&self.0);
-            assert_eq!(&res[..], &self.1[..]);
-        }
+    test_scale_codec_uint!(U128, 2, test_scale_codec_u128);
+    test_scale_codec_uint!(U256, 4, test_scale_codec_u256);
+    test_scale_codec_uint!(U512, 8, test_scale_codec_u512);
+    test_scale_codec_uint!(I128, 2, test_scale_codec_i128);
+    test_scale_codec_uint!(I256, 4, test_scale_codec_i256);
+    test_scale_codec_uint!(I512, 8, test_scale_codec_i512);
+
+    macro_rules! test_scale_codec_hash {
+        ($name: ident, $test_name: ident) => {
+            #[test]
+            fn $test_name() {
+                let value = $name::from_slice(
+                    &(0..$name::len_bytes()).map(|i| i as u8).collect::<Vec<_>>(),
+                );
+                assert_eq!(value.encode(), value.as_bytes().to_vec());
+                assert_eq!($name::decode(&mut value.as_bytes()), Ok(value));
+                assert!($name::decode(&mut &value.as_bytes()[1..]).is_err());
+            }
+        };
     }
 
-    struct DTestPair<T>(T, Vec<u8>);
+    test_scale_codec_hash!(H160, test_scale_codec_h160);
+    test_scale_codec_hash!(H256, test_scale_codec_h256);
+    test_scale_codec_hash!(H512, test_scale_codec_h512);
 
-    impl<T: rlp::Decodable + fmt::Debug + cmp::Eq> DTestPair<T> {
-        pub fn run_decode_test(self) {
-            let res: Result<T, rlp::DecoderError> = rlp::decode(&self.1);
-            assert_eq!(&res.unwrap(), &self.0);
+    #[test]
+    fn test_scale_codec_vec() {
+        let values = vec![H256::repeat_byte(1), H256::repeat_byte(2)];
+        let encoded = values.encode();
+        assert_eq!(encoded.len(), 1 + 64);
+        assert_eq!(Vec::<H256>::decode(&mut encoded.as_slice()), Ok(values));
+    }
+
+    /// Input which does not tell its remaining length.
+    struct UnknownLen<'a>(&'a [u8]);
+
+    impl Input for UnknownLen<'_> {
+        fn remaining_len(&mut self) -> Result<Option<usize>, scale_codec::Error> {
+            Ok(None)
+        }
+
+        fn read(&mut self, into: &mut [u8]) -> Result<(), scale_codec::Error> {
+            self.0.read(into)
         }
     }
 
     #[test]
-    fn test_rlp_codec_u256() {
+    fn test_scale_codec_slice() {
+        use crate::{SliceCodec, SliceView};
+
+        let hashes: Vec<H256> = (0..100u8).map(H256::repeat_byte).collect();
+        let encoded = hashes.encode();
+        assert_eq!(H256::encode_slice(&hashes), encoded);
+        assert_eq!(
+            H256::decode_vec(&mut encoded.as_slice()),
+            Ok(hashes.clone())
+        );
+
+        let mut input = &[&encoded[..], &[7]].concat()[..];
+        assert_eq!(H256::view_slice(&mut input), Ok(&hashes[..]));
+        assert_eq!(input, &[7]);
+
+        let uints: Vec<U256> = (0..100u64).map(|i| U256::max_value() / (i + 1)).collect();
+        let encoded = uints.encode();
+        assert_eq!(U256::encode_slice(&uints), encoded);
+        assert_eq!(U256::decode_vec(&mut encoded.as_slice()), Ok(uints.clone()));
+        // Inputs of unknown length are read in bounded chunks.
+        assert_eq!(
+            U256::decode_vec(&mut UnknownLen(encoded.as_slice())),
+            Ok(uints)
+        );
+        assert_eq!(
+            I256::encode_slice(&[I256::min_value()]),
+            vec![I256::min_value()].encode()
+        );
+
+        assert_eq!(H160::encode_slice(&[]), vec![0]);
+        assert_eq!(H160::decode_vec(&mut &[0u8][..]), Ok(vec![]));
+        assert_eq!(H160::view_slice(&mut &[0u8][..]), Ok(&[][..]));
+    }
+
+    #[test]
+    fn test_scale_codec_slice_invalid() {
+        use crate::{SliceCodec, SliceView};
+
+        let encoded = vec![H256::repeat_byte(1); 3].encode();
+        let short = &encoded[..encoded.len() - 1];
+        assert!(H256::decode_vec(&mut &short[..]).is_err());
+        assert!(H256::view_slice(&mut &short[..]).is_err());
+
+        let encoded = vec![U128::one(); 3].encode();
+        let short = &encoded[..encoded.len() - 1];
+        assert!(U128::decode_vec(&mut &short[..]).is_err());
+        assert!(U128::decode_vec(&mut UnknownLen(short)).is_err());
+
+        // A length prefix of `u32::MAX` elements must not be allocated up front.
+        let huge = [0xff, 0xff, 0xff, 0xff, 0xff, 1, 2, 3];
+        assert!(H512::decode_vec(&mut &huge[..]).is_err());
+        assert!(H512::view_slice(&mut &huge[..]).is_err());
+        assert!(U512::decode_vec(&mut &huge[..]).is_err());
+        assert!(U512::decode_vec(&mut UnknownLen(&huge[..])).is_err());
+    }
+
+    #[test]
+    fn test_scale_codec_wrappers() {
+        use crate::{BigEndian, FixedU256, LittleEndian, Saturating, Wrapping, D18};
+
+        let value = U256::from(0x0102);
+        let mut be = vec![0u8; 32];
+        be[30] = 1;
+        be[31] = 2;
+        assert_eq!(BigEndian(value).encode(), be);
+        assert_eq!(LittleEndian(value).encode(), value.encode());
+        assert_eq!(
+            BigEndian::<U256>::decode(&mut be.as_slice()),
+            Ok(BigEndian(value))
+        );
+        assert!(BigEndian::<U256>::decode(&mut &be[1..]).is_err());
+
+        assert_eq!(Wrapping(value).encode(), value.encode());
+        assert_eq!(
+            Saturating::<U256>::decode(&mut value.encode().as_slice()),
+            Ok(Saturating(value))
+        );
+
+        let fixed = FixedU256::<D18>::from_inner(value);
+        assert_eq!(fixed.encode(), value.encode());
+        assert_eq!(
+            FixedU256::<D18>::decode(&mut value.encode().as_slice()),
+            Ok(fixed)
+        );
+    }
+
+    #[test]
+    fn test_scale_codec_fixed_array() {
+        #[derive(Debug, PartialEq)]
+        struct Limbs([u64; 2]);
+        impl_codec::impl_fixed_array_scale_codec!(Limbs, u64, 2);
+
+        // The bytes are fixed, whatever the byte order of the host.
+        let limbs = Limbs([0x0102, u64::max_value() - 1]);
+        let mut encoded = vec![2, 1, 0, 0, 0, 0, 0, 0];
+        encoded.extend_from_slice(&[0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]);
+        assert_eq!(limbs.encode(), encoded);
+        assert_eq!(limbs.size_hint(), 16);
+        assert_eq!(Limbs::decode(&mut encoded.as_slice()), Ok(limbs));
+        assert!(Limbs::decode(&mut &encoded[1..]).is_err());
+    }
+
+    #[test]
+    fn test_scale_codec_error() {
         let tests = vec![
-            (U256::from(0u64), vec![0x80u8]),
-            (
-                U256::from(0x0100_0000u64),
-                vec![0x84, 0x01, 0x00, 0x00, 0x00],
-            ),
+            (Error::Overflow, vec![0]),
             (
-                U256::from(0xffff_ffffu64),
-                vec![0x84, 0xff, 0xff, 0xff, 0xff],
+                Error::InvalidLength {
+                    expected: 32,
+                    actual: 20,
+                },
+                vec![3, 32, 0, 0, 0, 0, 0, 0, 0, 20, 0, 0, 0, 0, 0, 0, 0],
             ),
             (
-                U256::from(
-                    helper::from_hex_str(
-                        "0x8090a0b0c0d0e0f00910203040506077000000000000000100000000000012f0",
-                    )
-                    .as_slice(),
-                ),
-                vec![
-                    0xa0, 0x80, 0x90, 0xa0, 0xb0, 0xc0, 0xd0, 0xe0, 0xf0, 0x09, 0x10, 0x20, 0x30,
-                    0x40, 0x50, 0x60, 0x77, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00,
-                    0x00, 0x00, 0x00, 0x00, 0x00, 0x12, 0xf0,
-                ],
+                Error::InvalidHexCharacter(7),
+                vec![4, 7, 0, 0, 0, 0, 0, 0, 0],
             ),
+            (Error::DivisionByZero, vec![6]),
         ];
-        for (arg0, arg1) in tests {
-            ETestPair(arg0, arg1.clone()).run_encode_test();
-            DTestPair(arg0, arg1).run_decode_test();
+        for (error, encoded) in tests {
+            assert_eq!(error.encode(), encoded);
+            assert_eq!(Error::decode(&mut encoded.as_slice()), Ok(error));
         }
+        assert!(Error::decode(&mut &[7u8][..]).is_err());
+        assert!(Error::decode(&mut &[4u8, 7][..]).is_err());
     }
 
     #[test]
-    fn test_rlp_codec_h160() {
-        let tests = vec![(
-            H160::from_slice(&helper::from_hex_str(
-                "0xef2d6d194084c2de36e0dabfce45d046b37d1106",
-            )),
-            vec![
-                0x94, 0xef, 0x2d, 0x6d, 0x19, 0x40, 0x84, 0xc2, 0xde, 0x36, 0xe0, 0xda, 0xbf, 0xce,
-                0x45, 0xd0, 0x46, 0xb3, 0x7d, 0x11, 0x06,
-            ],
-        )];
-        for (arg0, arg1) in tests {
-            ETestPair(arg0, arg1.clone()).run_encode_test();
-            DTestPair(arg0, arg1).run_decode_test();
+    fn test_scale_codec_compact() {
+        use crate::CompactUint;
+        use impl_codec::scale_codec::Compact;
+
+        let mut values = vec![0u128, 1, u128::max_value()];
+        for shift in 0..128 {
+            values.push(1 << shift);
+            values.push((1 << shift) - 1);
+            values.push((1 << shift) | 1);
+        }
+        for value in values {
+            let expected = Compact(value).encode();
+            for encoded in vec![
+                CompactUint(U128::from(value)).encode(),
+                CompactUint(U256::from(value)).encode(),
+                CompactUint(U512::from(value)).encode(),
+            ] {
+                assert_eq!(encoded, expected, "{}", value);
+            }
+            assert_eq!(CompactUint(U256::from(value)).size_hint(), expected.len());
+            assert_eq!(
+                CompactUint::<U128>::decode(&mut expected.as_slice()),
+                Ok(CompactUint(U128::from(value)))
+            );
+            assert_eq!(
+                CompactUint::<U512>::decode(&mut expected.as_slice()),
+                Ok(CompactUint(U512::from(value)))
+            );
+        }
+
+        for value in vec![U256::max_value(), U256::one() << 128, U256::one() << 200] {
+            let encoded = CompactUint(value).encode();
+            let len = (value.bits() + 7) / 8;
+            assert_eq!(encoded.len(), 1 + len);
+            assert_eq!(usize::from(encoded[0]), ((len - 4) << 2) | 0b11);
+            assert_eq!(
+                CompactUint::<U256>::decode(&mut encoded.as_slice()),
+                Ok(CompactUint(value))
+            );
+            assert!(CompactUint::<U128>::decode(&mut encoded.as_slice()).is_err());
+            assert!(Compact::<u128>::decode(&mut encoded.as_slice()).is_err());
+        }
+        let encoded = CompactUint(U512::max_value()).encode();
+        assert_eq!(encoded[0], 0b1111_0011);
+        assert_eq!(
+            CompactUint::<U512>::decode(&mut encoded.as_slice()),
+            Ok(CompactUint(U512::max_value()))
+        );
+
+        // Non-canonical, unsupported and truncated encodings.
+        for encoded in vec![
+            vec![0b01, 0],
+            vec![0b10, 0, 0, 0],
+            vec![0b11, 0xff, 0xff, 0xff, 0x3f],
+            vec![0b111, 1, 0, 0, 0, 0],
+            vec![0b1111_0111],
+            vec![0b101, 0],
+            vec![0b11, 0, 0, 0],
+        ] {
+            assert!(
+                CompactUint::<U512>::decode(&mut encoded.as_slice()).is_err(),
+                "{:?}",
+                encoded
+            );
         }
     }
-}
 
-#[test]
-fn test_fixed_arrays_roundtrip() {
-    use crate::U256;
-    let raw = U256::from(helper::from_hex_str("0x7094875209347850239487502394881").as_slice());
-    let array: [u8; 32] = raw.into();
-    let new_raw = array.into();
-    assert_eq!(raw, new_raw);
+    #[test]
+    fn test_scale_codec_max_encoded_len() {
+        use crate::{BigEndian, CompactUint, FixedU256, Saturating, Wrapping, D18};
+        use impl_codec::MaxEncodedLen;
+
+        assert_eq!(U128::max_encoded_len(), U128::max_value().encode().len());
+        assert_eq!(U256::max_encoded_len(), 32);
+        assert_eq!(U512::max_encoded_len(), 64);
+        assert_eq!(I256::max_encoded_len(), I256::min_value().encode().len());
+        assert_eq!(H160::max_encoded_len(), 20);
+        assert_eq!(H512::max_encoded_len(), 64);
+        assert_eq!(BigEndian::<U512>::max_encoded_len(), 64);
+        assert_eq!(Wrapping::<U256>::max_encoded_len(), 32);
+        assert_eq!(Saturating::<U128>::max_encoded_len(), 16);
+        assert_eq!(FixedU256::<D18>::max_encoded_len(), 32);
+        for value in vec![U256::max_value(), U256::zero()] {
+            assert!(CompactUint(value).encode().len() <= CompactUint::<U256>::max_encoded_len());
+        }
+        assert_eq!(
+            CompactUint::<U512>::max_encoded_len(),
+            CompactUint(U512::max_value()).encode().len()
+        );
+        let error = Error::InvalidLength {
+            expected: usize::max_value(),
+            actual: 0,
+        };
+        assert_eq!(Error::max_encoded_len(), error.encode().len());
+    }
+
+    #[cfg(feature = "codec")]
+    #[test]
+    fn test_scale_codec_same_layout() {
+        use impl_codec::codec;
+
+        let value = U512::max_value() - 0x0102;
+        assert_eq!(value.encode(), codec::Encode::encode(&value));
+        let hash = H160::repeat_byte(0xab);
+        assert_eq!(hash.encode(), codec::Encode::encode(&hash));
+        let error = Error::InvalidLength {
+            expected: 32,
+            actual: 20,
+        };
+        assert_eq!(error.encode(), codec::Encode::encode(&error));
+    }
 }
 
-#[test]
-#[allow(clippy::cognitive_complexity)]
-fn test_u256_multi_full_mul() {
-    use core::u64::MAX;
+#[cfg(feature = "scale-info")]
+mod scale_info_tests {
+    #[cfg(not(feature = "std"))]
+    use alloc::{vec, vec::Vec};
 
-    use crate::{U256, U512};
+    use scale_info_crate::{MetaType, Type, TypeDef, TypeInfo};
 
-    assert_eq!(
-        U512([0, 0, 0, 0, 0, 0, 0, 0]),
-        U256([0, 0, 0, 0]).full_mul(U256([0, 0, 0, 0]))
-    );
-    assert_eq!(
-        U512([1, 0, 0, 0, 0, 0, 0, 0]),
-        U256([1, 0, 0, 0]).full_mul(U256([1, 0, 0, 0]))
-    );
-    assert_eq!(
-        U512([25, 0, 0, 0, 0, 0, 0, 0]),
-        U256([5, 0, 0, 0]).full_mul(U256([5, 0, 0, 0]))
-    );
-    assert_eq!(
-        U512([0, 0, 25, 0, 0, 0, 0, 0]),
-        U256([0, 5, 0, 0]).full_mul(U256([0, 5, 0, 0]))
-    );
-    assert_eq!(
-        U512([0, 0, 0, 16, 0, 0, 0, 0]),
-        U256([0, 0, 0, 4]).full_mul(U256([4, 0, 0, 0]))
-    );
-    assert_eq!(
-        U512([0, 0, 0, 10, 0, 0, 0, 0]),
+    use crate::{BigEndian, Error, Wrapping, H160, H256, H512, I128, I256, I512, U128, U256, U512};
+
+    fn byte_array<T: TypeInfo>(len: usize) -> (Type, Vec<&'static str>) {
+        let ty = T::type_info();
+        match ty.type_def() {
+            TypeDef::Composite(composite) => {
+                assert_eq!(composite.fields().len(), 1);
+                let field = composite.fields()[0].ty();
+                match field.type_info().type_def() {
+                    TypeDef::Array(array) => {
+                        assert_eq!(array.len() as usize, len);
+                        assert_eq!(*array.type_param(), MetaType::new::<u8>());
+                    }
+                    def => panic!("unexpected field {:?}", def),
+                }
+            }
+            def => panic!("unexpected type {:?}", def),
+        }
+        let docs = ty.docs().to_vec();
+        (ty, docs)
+    }
+
+    #[test]
+    fn test_scale_info_byte_arrays() {
+        let little_endian = vec!["Integer encoded as a little-endian byte array."];
+        for (ty, docs) in vec![
+            byte_array::<U128>(16),
+            byte_array::<U256>(32),
+            byte_array::<U512>(64),
+            byte_array::<I128>(16),
+            byte_array::<I256>(32),
+            byte_array::<I512>(64),
+        ] {
+            assert_eq!(ty.path().namespace(), &["primitive_types"]);
+            assert_eq!(docs, little_endian);
+        }
+        for (ty, docs) in vec![
+            byte_array::<H160>(20),
+            byte_array::<H256>(32),
+            byte_array::<H512>(64),
+        ] {
+            assert_eq!(ty.path().namespace(), &["primitive_types"]);
+            assert_eq!(docs, vec!["Fixed-size hash encoded as a byte array."]);
+        }
+        assert_eq!(U256::type_info().path().ident(), Some("U256"));
+        assert_eq!(H160::type_info().path().ident(), Some("H160"));
+
+        let (ty, docs) = byte_array::<BigEndian<U256>>(32);
+        assert_eq!(ty.path().ident(), Some("BigEndian"));
+        assert_eq!(ty.type_params()[0].ty(), Some(&MetaType::new::<U256>()));
+        assert_eq!(docs, vec!["Uint encoded as a big-endian byte array."]);
+        assert_ne!(
+            MetaType::new::<BigEndian<U256>>(),
+            MetaType::new::<BigEndian<U128>>()
+        );
+    }
+
+    #[test]
+    fn test_scale_info_wrappers() {
+        match Wrapping::<U256>::type_info().type_def() {
+            TypeDef::Composite(composite) => {
+                assert_eq!(*composite.fields()[0].ty(), MetaType::new::<U256>())
+            }
+            def => panic!("unexpected type {:?}", def),
+        }
+
+        #[cfg(feature = "scale-codec")]
+        match crate::CompactUint::<U256>::type_info().type_def() {
+            TypeDef::Compact(compact) => assert_eq!(*compact.type_param(), MetaType::new::<U256>()),
+            def => panic!("unexpected type {:?}", def),
+        }
+
+        match Error::type_info().type_def() {
+            TypeDef::Variant(variant) => {
+                let variants = variant
+                    .variants()
+                    .iter()
+                    .map(|v| (*v.name(), v.index(), v.fields().len()))
+                    .collect::<Vec<_>>();
+                assert_eq!(
+                    variants,
+                    vec![
+                        ("Overflow", 0, 0),
+                        ("Underflow", 1, 0),
+                        ("Negative", 2, 0),
+                        ("InvalidLength", 3, 2),
+                        ("InvalidHexCharacter", 4, 1),
+                        ("InvalidDecimalDigit", 5, 0),
+                        ("DivisionByZero", 6, 0),
+                    ]
+                );
+            }
+            def => panic!("unexpected type {:?}", def),
+        }
+    }
+
+    #[test]
+    fn test_scale_info_registry() {
+        use scale_info_crate::{PortableRegistry, Registry};
+
+        let mut registry = Registry::new();
+        registry.register_type(&MetaType::new::<U256>());
+        registry.register_type(&MetaType::new::<H256>());
+        registry.register_type(&MetaType::new::<Wrapping<U256>>());
+        // `U256`, `[u8; 32]`, `u8`, `H256` and `Wrapping<U256>`.
+        assert_eq!(PortableRegistry::from(registry).types().len(), 5);
+    }
+}
+
+#[cfg(feature = "subtle")]
+mod subtle_tests {
+    use subtle::{ConditionallySelectable, ConstantTimeEq};
+
+    use crate::{H160, H256, H512, U128, U256, U512};
+
+    macro_rules! test_constant_time {
+        ($name: ident, $test_name: ident, $a: expr, $b: expr) => {
+            #[test]
+            fn $test_name() {
+                let (a, b): ($name, $name) = ($a, $b);
+                assert!(a < b);
+                assert!(bool::from(a.ct_eq(&a)));
+                assert!(!bool::from(a.ct_eq(&b)));
+                assert!(bool::from(b.ct_gt(&a)));
+                assert!(!bool::from(a.ct_gt(&b)));
+                assert!(!bool::from(a.ct_gt(&a)));
+                assert!(bool::from(a.ct_lt(&b)));
+                assert!(!bool::from(b.ct_lt(&a)));
+                assert!(!bool::from(a.ct_lt(&a)));
+                assert_eq!($name::conditional_select(&a, &b, 0.into()), a);
+                assert_eq!($name::conditional_select(&a, &b, 1.into()), b);
+            }
+        };
+    }
+
+    test_constant_time!(
+        H160,
+        test_constant_time_h160,
+        H160::zero(),
+        H160::repeat_byte(1)
+    );
+    test_constant_time!(
+        H256,
+        test_constant_time_h256,
+        H256::from_low_u64_be(0xff),
+        H256::from_low_u64_be(0x100)
+    );
+    test_constant_time!(
+        H512,
+        test_constant_time_h512,
+        H512::from_low_u64_be(u64::max_value()),
+        H512::repeat_byte(0x80)
+    );
+    test_constant_time!(
+        U128,
+        test_constant_time_u128,
+        U128::from(2),
+        U128::max_value()
+    );
+    test_constant_time!(
+        U256,
+        test_constant_time_u256,
+        U256::from(u64::max_value()),
+        U256::one() << 64
+    );
+    test_constant_time!(
+        U512,
+        test_constant_time_u512,
+        U512::max_value() - 1,
+        U512::max_value()
+    );
+
+    #[test]
+    fn test_constant_time_ordering_matches_ord() {
+        let to_hash = |value: U128| {
+            let mut bytes = [0u8; 20];
+            value.to_big_endian(&mut bytes[4..]);
+            H160(bytes)
+        };
+        let words = [
+            0,
+            1,
+            0x7fff_ffff_ffff_ffff,
+            0x8000_0000_0000_0000,
+            u64::max_value(),
+        ];
+        for &high_a in &words {
+            for &low_a in &words {
+                for &high_b in &words {
+                    for &low_b in &words {
+                        let (a, b) = (U128([low_a, high_a]), U128([low_b, high_b]));
+                        assert_eq!(bool::from(a.ct_gt(&b)), a > b);
+                        assert_eq!(bool::from(a.ct_lt(&b)), a < b);
+                        let (a, b) = (to_hash(a), to_hash(b));
+                        assert_eq!(bool::from(a.ct_gt(&b)), a > b);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_constant_time_newtype() {
+        #[derive(Debug)]
+        struct SessionKey(H256);
+
+        crate::impl_constant_time_eq!(SessionKey);
+
+        let key = SessionKey(H256::repeat_byte(7));
+        assert_eq!(key, SessionKey(H256::repeat_byte(7)));
+        assert_ne!(key, SessionKey(H256::zero()));
+        assert!(bool::from(key.ct_eq(&SessionKey(H256::repeat_byte(7)))));
+    }
+}
+
+#[cfg(feature = "num-traits")]
+mod num_traits_tests {
+    use num_traits::{
+        pow::Pow, Bounded, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, FromPrimitive, Num, One,
+        Saturating, ToPrimitive, Unsigned, WrappingAdd, WrappingMul, WrappingSub, Zero,
+    };
+
+    use crate::{Error, U128, U256, U512};
+
+    fn sum_of_squares<T: Unsigned + Copy + CheckedAdd + CheckedMul>(values: &[T]) -> Option<T> {
+        values
+            .iter()
+            .try_fold(T::zero(), |acc, v| acc.checked_add(&v.checked_mul(v)?))
+    }
+
+    #[test]
+    fn test_num_traits_generic() {
+        let values = [U256::from(3), U256::from(4)];
+        assert_eq!(sum_of_squares(&values), Some(U256::from(25)));
+        assert_eq!(sum_of_squares(&[3u64, 4]), Some(25));
+        assert_eq!(sum_of_squares(&[U128::max_value()]), None);
+
+        assert!(<U256 as Zero>::is_zero(&Zero::zero()));
+        assert_eq!(<U512 as One>::one(), U512::one());
+        assert_eq!(<U128 as Bounded>::min_value(), U128::zero());
+        assert_eq!(<U128 as Bounded>::max_value(), U128::max_value());
+    }
+
+    #[test]
+    fn test_num_traits_ops() {
+        let max = U256::max_value();
+        let one = U256::one();
+        assert_eq!(CheckedAdd::checked_add(&max, &one), None);
+        assert_eq!(CheckedSub::checked_sub(&one, &max), None);
+        assert_eq!(CheckedMul::checked_mul(&max, &one), Some(max));
+        assert_eq!(CheckedDiv::checked_div(&max, &U256::zero()), None);
+        assert_eq!(Saturating::saturating_add(max, one), max);
+        assert_eq!(Saturating::saturating_sub(one, max), U256::zero());
+        assert_eq!(WrappingAdd::wrapping_add(&max, &one), U256::zero());
+        assert_eq!(WrappingSub::wrapping_sub(&U256::zero(), &one), max);
+        assert_eq!(WrappingMul::wrapping_mul(&max, &max), one);
+        assert_eq!(Pow::pow(U256::from(2), 10u32), U256::from(1024));
+        assert_eq!(Pow::pow(U256::from(2), U256::from(255)), one << 255);
+    }
+
+    #[test]
+    fn test_num_traits_primitive() {
+        assert_eq!(U256::from(7).to_u64(), Some(7));
+        assert_eq!(U256::from(7).to_i8(), Some(7));
+        assert_eq!(U256::from(u64::max_value()).to_i64(), None);
+        assert_eq!((U256::one() << 64).to_u64(), None);
+        assert_eq!((U256::one() << 64).to_u128(), Some(1 << 64));
+        assert_eq!((U256::one() << 127).to_i128(), None);
+        assert_eq!(U256::max_value().to_u128(), None);
+        assert_eq!((U256::one() << 200).to_f64(), Some(2f64.powi(200)));
+        assert_eq!(U512::max_value().to_f64(), Some(2f64.powi(512)));
+        assert_eq!(U128::zero().to_f32(), Some(0.0));
+        assert_eq!(U256::max_value().to_f32(), Some(core::f32::INFINITY));
+
+        // The casts from `u128` round correctly, also where rounding the limbs
+        // separately or through `f64` would round twice.
+        for &value in [
+            1u128,
+            u128::from(u64::max_value()),
+            (1 << 53) + 1,
+            ((1 << 53) + 1) << 64,
+            (((1 << 53) + 1) << 64) + 1,
+            (1 << 64) + (1 << 40) + 1,
+            (1 << 64) + (1 << 40),
+            u128::max_value(),
+        ]
+        .iter()
+        {
+            assert_eq!(U128::from(value).to_f64(), Some(value as f64));
+            assert_eq!(U128::from(value).to_f32(), Some(value as f32));
+            assert_eq!(
+                (U256::from(value) << 128).to_f64(),
+                Some(value as f64 * 2f64.powi(128))
+            );
+        }
+
+        assert_eq!(U256::from_u64(7), Some(U256::from(7)));
+        assert_eq!(U256::from_i64(-7), None);
+        assert_eq!(U256::from_i8(7), Some(U256::from(7)));
+        assert_eq!(
+            U256::from_u128(u128::max_value()),
+            Some(U256::from(u128::max_value()))
+        );
+        assert_eq!(U256::from_i128(-1), None);
+        assert_eq!(U256::from_f64(1e9), Some(U256::from(1_000_000_000)));
+        assert_eq!(U256::from_f64(-1.0), None);
+    }
+
+    #[test]
+    fn test_num_traits_from_str_radix() {
+        assert_eq!(U256::from_str_radix("ff", 16), Ok(U256::from(255)));
+        assert_eq!(U256::from_str_radix("FF", 16), Ok(U256::from(255)));
+        assert_eq!(U256::from_str_radix("101", 2), Ok(U256::from(5)));
+        assert_eq!(U256::from_str_radix("zz", 36), Ok(U256::from(1295)));
+        assert_eq!(
+            U256::from_str_radix("12345678901234567890", 10),
+            Ok(U256::from(12_345_678_901_234_567_890u64))
+        );
+        assert_eq!(
+            U128::from_str_radix("340282366920938463463374607431768211455", 10),
+            Ok(U128::max_value())
+        );
+        assert_eq!(
+            U128::from_str_radix("340282366920938463463374607431768211456", 10),
+            Err(Error::Overflow)
+        );
+        assert_eq!(
+            U256::from_str_radix("0x12", 16),
+            Err(Error::InvalidHexCharacter(1))
+        );
+        assert_eq!(
+            U256::from_str_radix("12", 2),
+            Err(Error::InvalidDecimalDigit)
+        );
+        assert_eq!(
+            U256::from_str_radix("", 10),
+            Err(Error::InvalidDecimalDigit)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "radix must be in the range 2..=36")]
+    fn test_num_traits_invalid_radix() {
+        let _ = U256::from_str_radix("1", 37);
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+mod bigint_tests {
+    use core::convert::TryFrom;
+
+    use num_bigint::BigUint;
+    use quickcheck::{quickcheck, Arbitrary, Gen};
+
+    use crate::{Error, SaturatingFrom, TruncatingFrom, U1024, U128, U256, U512};
+
+    fn below<G: Gen>(g: &mut G, bound: usize) -> usize {
+        (g.next_u64() % bound as u64) as usize
+    }
+
+    macro_rules! impl_arbitrary {
+        ($name:ident, $n_words:tt) => {
+            /// Random values of random bit length, mixed with edge cases around
+            /// zero, the maximum and the powers of two.
+            impl Arbitrary for $name {
+                fn arbitrary<G: Gen>(g: &mut G) -> Self {
+                    let bits = below(g, $n_words * 64 + 1);
+                    let edge = match bits {
+                        0 => $name::zero(),
+                        _ => $name::one() << (bits - 1),
+                    };
+                    match below(g, 8) {
+                        0 => edge,
+                        1 => edge.overflowing_sub($name::one()).0,
+                        2 => $name::max_value() - edge,
+                        _ => {
+                            let mut words = [0u64; $n_words];
+                            for word in words.iter_mut() {
+                                *word = g.next_u64();
+                            }
+                            match bits {
+                                0 => $name::zero(),
+                                _ => $name(words) >> ($n_words * 64 - bits),
+                            }
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    impl_arbitrary!(U128, 2);
+    impl_arbitrary!(U256, 4);
+    impl_arbitrary!(U512, 8);
+
+    fn big_u1024(value: U1024) -> BigUint {
+        let mut bytes = [0u8; 128];
+        value.to_little_endian(&mut bytes);
+        BigUint::from_bytes_le(&bytes)
+    }
+
+    fn modulus(bits: usize) -> BigUint {
+        BigUint::from(1u8) << bits
+    }
+
+    /// Computes `base ^ exp` and reports whether it fits in `bits`.
+    fn big_pow(base: &BigUint, exp: u32, bits: usize) -> Option<BigUint> {
+        let mut result = BigUint::from(1u8);
+        for _ in 0..exp {
+            result = result * base;
+            if result.bits() > bits {
+                return None;
+            }
+        }
+        Some(result)
+    }
+
+    macro_rules! test_against_bigint {
+        ($name:ident, $n_words:tt, $wide:ident, $mod_name:ident) => {
+            mod $mod_name {
+                use super::*;
+
+                const BITS: usize = $n_words * 64;
+
+                fn big(value: $name) -> BigUint {
+                    BigUint::from(value)
+                }
+
+                fn from_big(value: BigUint) -> $name {
+                    $name::try_from(value).unwrap()
+                }
+
+                quickcheck! {
+                    fn roundtrip(a: $name) -> bool {
+                        $name::try_from(big(a)) == Ok(a)
+                    }
+
+                    fn add(a: $name, b: $name) -> bool {
+                        let sum = big(a) + big(b);
+                        let overflow = sum.bits() > BITS;
+                        a.overflowing_add(b) == (from_big(sum % modulus(BITS)), overflow)
+                    }
+
+                    fn sub(a: $name, b: $name) -> bool {
+                        match a.checked_sub(b) {
+                            Some(difference) => big(difference) + big(b) == big(a),
+                            None => a < b,
+                        }
+                    }
+
+                    fn mul(a: $name, b: $name) -> bool {
+                        let product = big(a) * big(b);
+                        let overflow = product.bits() > BITS;
+                        a.overflowing_mul(b) == (from_big(product % modulus(BITS)), overflow)
+                    }
+
+                    fn full_mul(a: $name, b: $name) -> bool {
+                        big_full(a.full_mul(b)) == big(a) * big(b)
+                    }
+
+                    fn div_rem(a: $name, b: $name) -> bool {
+                        if b.is_zero() {
+                            return a.checked_div(b).is_none() && a.checked_rem(b).is_none();
+                        }
+                        let (quotient, remainder) = a.div_mod(b);
+                        big(quotient) == big(a) / big(b) && big(remainder) == big(a) % big(b)
+                    }
+
+                    fn pow(base: $name, exp: u32) -> bool {
+                        let exp = exp % 300;
+                        let expected = big_pow(&big(base), exp, BITS).map(from_big);
+                        base.checked_pow($name::from(exp)) == expected
+                    }
+
+                    fn shl(a: $name, shift: usize) -> bool {
+                        let shift = shift % BITS;
+                        big(a << shift) == (big(a) << shift) % modulus(BITS)
+                    }
+
+                    fn shr(a: $name, shift: usize) -> bool {
+                        let shift = shift % BITS;
+                        big(a >> shift) == big(a) >> shift
+                    }
+
+                    fn widen(a: $name) -> bool {
+                        big_full($wide::from(a)) == big(a)
+                            && U1024::from(a) == U1024::from_little_endian(&big(a).to_bytes_le())
+                    }
+                }
+
+                fn big_full(value: $wide) -> BigUint {
+                    $wide::try_from(big_u1024(U1024::from(value)))
+                        .map(BigUint::from)
+                        .unwrap()
+                }
+            }
+        };
+    }
+
+    test_against_bigint!(U128, 2, U256, u128_ops);
+    test_against_bigint!(U256, 4, U512, u256_ops);
+
+    quickcheck! {
+        fn u512_ops(a: U512, b: U512, shift: usize) -> bool {
+            let (a_big, b_big) = (BigUint::from(a), BigUint::from(b));
+            let shift = shift % 512;
+            big_u1024(a.full_mul(b)) == &a_big * &b_big
+                && BigUint::from(a << shift) == (&a_big << shift) % modulus(512)
+                && BigUint::from(a >> shift) == &a_big >> shift
+                && (b.is_zero() || BigUint::from(a / b) == &a_big / &b_big)
+                && (b.is_zero() || BigUint::from(a % b) == &a_big % &b_big)
+                && U512::try_from(big_u1024(U1024::from(a))) == Ok(a)
+        }
+
+        fn narrowing(a: U512) -> bool {
+            let big = BigUint::from(a);
+            let low_128 = &big % modulus(128);
+            let low_256 = &big % modulus(256);
+            let fits_128 = big.bits() <= 128;
+            let fits_256 = big.bits() <= 256;
+
+            U128::try_from(a).ok().map(BigUint::from) == Some(big.clone()).filter(|_| fits_128)
+                && U256::try_from(a).ok().map(BigUint::from) == Some(big.clone()).filter(|_| fits_256)
+                && BigUint::from(U128::truncating_from(a)) == low_128
+                && BigUint::from(U256::truncating_from(a)) == low_256
+                && BigUint::from(U128::saturating_from(a))
+                    == if fits_128 { big.clone() } else { modulus(128) - 1u8 }
+                && BigUint::from(U256::saturating_from(a))
+                    == if fits_256 { big.clone() } else { modulus(256) - 1u8 }
+                && U128::truncating_from(U256::truncating_from(a)) == U128::truncating_from(a)
+                && u128::truncating_from(a) == U128::truncating_from(a).low_u128()
+        }
+    }
+
+    #[test]
+    fn test_bigint_conversions() {
+        assert_eq!(BigUint::from(U256::zero()), BigUint::from(0u8));
+        assert_eq!(BigUint::from(U128::max_value()), modulus(128) - 1u8);
+        assert_eq!(U256::try_from(modulus(256) - 1u8), Ok(U256::max_value()));
+        assert_eq!(U256::try_from(modulus(256)), Err(Error::Overflow));
+        assert_eq!(U128::try_from(modulus(128)), Err(Error::Overflow));
+        assert_eq!(
+            U512::try_from(BigUint::from(u64::max_value())),
+            Ok(U512::from(u64::max_value()))
+        );
+    }
+}
+
+#[cfg(any(feature = "upstream-primitive-types", feature = "ethereum-types"))]
+mod upstream_tests {
+    #[cfg(not(feature = "upstream-primitive-types"))]
+    use ethereum_types as upstream;
+    #[cfg(feature = "upstream-primitive-types")]
+    use upstream_primitive_types as upstream;
+
+    use crate::{H160, H256, H512, U128, U256, U512};
+
+    macro_rules! test_upstream {
+        ($name: ident, $test_name: ident, $value: expr) => {
+            #[test]
+            fn $test_name() {
+                let value: $name = $value;
+                let theirs = upstream::$name::from(value);
+                assert_eq!(theirs.0, value.0);
+                assert_eq!($name::from(theirs), value);
+
+                let ours = [value, $name::default(), value];
+                let viewed = $name::as_upstream_slice(&ours);
+                assert_eq!(viewed, &[theirs, upstream::$name::default(), theirs][..]);
+                assert_eq!(viewed.as_ptr() as usize, ours.as_ptr() as usize);
+                assert_eq!($name::from_upstream_slice(viewed), &ours[..]);
+                assert!($name::as_upstream_slice(&[]).is_empty());
+            }
+        };
+    }
+
+    test_upstream!(H160, test_upstream_h160, H160::repeat_byte(0x12));
+    test_upstream!(H256, test_upstream_h256, H256::repeat_byte(0x34));
+    test_upstream!(H512, test_upstream_h512, H512::repeat_byte(0x56));
+    test_upstream!(U128, test_upstream_u128, U128::max_value() - 1);
+    test_upstream!(U256, test_upstream_u256, U256::max_value() - 1);
+    test_upstream!(U512, test_upstream_u512, U512::max_value() - 1);
+
+    #[cfg(feature = "ethereum-types")]
+    #[test]
+    fn test_ethereum_address() {
+        let address = H160::repeat_byte(0xab);
+        let theirs: ethereum_types::Address = address.into();
+        assert_eq!(theirs, ethereum_types::Address::repeat_byte(0xab));
+        assert_eq!(H160::from(theirs), address);
+    }
+}
+
+#[cfg(feature = "rlp")]
+mod rlp_tests {
+    #[cfg(not(feature = "std"))]
+    use alloc::{vec, vec::Vec};
+    use core::{cmp, fmt};
+
+    use impl_rlp::rlp;
+
+    use super::helper;
+    use crate::{H160, I256, U256};
+
+    struct ETestPair<T>(T, Vec<u8>);
+
+    impl<T: rlp::Encodable> ETestPair<T> {
+        pub fn run_encode_test(self) {
+            let res = rlp::encode(&self.0);
+            assert_eq!(&res[..], &self.1[..]);
+        }
+    }
+
+    struct DTestPair<T>(T, Vec<u8>);
+
+    impl<T: rlp::Decodable + fmt::Debug + cmp::Eq> DTestPair<T> {
+        pub fn run_decode_test(self) {
+            let res: Result<T, rlp::DecoderError> = rlp::decode(&self.1);
+            assert_eq!(&res.unwrap(), &self.0);
+        }
+    }
+
+    #[test]
+    fn test_rlp_codec_u256() {
+        let tests = vec![
+            (U256::from(0u64), vec![0x80u8]),
+            (
+                U256::from(0x0100_0000u64),
+                vec![0x84, 0x01, 0x00, 0x00, 0x00],
+            ),
+            (
+                U256::from(0xffff_ffffu64),
+                vec![0x84, 0xff, 0xff, 0xff, 0xff],
+            ),
+            (
+                U256::from(
+                    helper::from_hex_str(
+                        "0x8090a0b0c0d0e0f00910203040506077000000000000000100000000000012f0",
+                    )
+                    .as_slice(),
+                ),
+                vec![
+                    0xa0, 0x80, 0x90, 0xa0, 0xb0, 0xc0, 0xd0, 0xe0, 0xf0, 0x09, 0x10, 0x20, 0x30,
+                    0x40, 0x50, 0x60, 0x77, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x12, 0xf0,
+                ],
+            ),
+        ];
+        for (arg0, arg1) in tests {
+            ETestPair(arg0, arg1.clone()).run_encode_test();
+            DTestPair(arg0, arg1).run_decode_test();
+        }
+    }
+
+    #[test]
+    fn test_rlp_codec_i256() {
+        let tests = vec![
+            (I256::from(0), vec![0x80u8]),
+            (I256::from(0x7f), vec![0x7f]),
+            (
+                I256::from(0xffff_ffffu64),
+                vec![0x84, 0xff, 0xff, 0xff, 0xff],
+            ),
+            (I256::from(-1), {
+                let mut bytes = vec![0xa0];
+                bytes.extend_from_slice(&[0xff; 32]);
+                bytes
+            }),
+        ];
+        for (arg0, arg1) in tests {
+            ETestPair(arg0, arg1.clone()).run_encode_test();
+            DTestPair(arg0, arg1).run_decode_test();
+        }
+    }
+
+    #[test]
+    fn test_rlp_codec_fixed() {
+        use crate::{FixedU256, D18};
+
+        let value = FixedU256::<D18>::from_inner(U256::from(0xffff_ffffu64));
+        ETestPair(value, vec![0x84, 0xff, 0xff, 0xff, 0xff]).run_encode_test();
+        DTestPair(value, vec![0x84, 0xff, 0xff, 0xff, 0xff]).run_decode_test();
+    }
+
+    #[test]
+    fn test_rlp_codec_h160() {
+        let tests = vec![(
+            H160::from_slice(&helper::from_hex_str(
+                "0xef2d6d194084c2de36e0dabfce45d046b37d1106",
+            )),
+            vec![
+                0x94, 0xef, 0x2d, 0x6d, 0x19, 0x40, 0x84, 0xc2, 0xde, 0x36, 0xe0, 0xda, 0xbf, 0xce,
+                0x45, 0xd0, 0x46, 0xb3, 0x7d, 0x11, 0x06,
+            ],
+        )];
+        for (arg0, arg1) in tests {
+            ETestPair(arg0, arg1.clone()).run_encode_test();
+            DTestPair(arg0, arg1).run_decode_test();
+        }
+    }
+
+    #[test]
+    fn test_rlp_codec_wrapping() {
+        use crate::{Saturating, Wrapping};
+
+        let value = U256::from(0x0102);
+        ETestPair(Wrapping(value), vec![0x82, 0x01, 0x02]).run_encode_test();
+        DTestPair(Wrapping(value), vec![0x82, 0x01, 0x02]).run_decode_test();
+        ETestPair(Saturating(value), vec![0x82, 0x01, 0x02]).run_encode_test();
+        DTestPair(Saturating(value), vec![0x82, 0x01, 0x02]).run_decode_test();
+    }
+
+    #[test]
+    fn test_rlp_codec_endian() {
+        use crate::{BigEndian, LittleEndian, U128};
+
+        let value = U128::from(0x0102);
+        let mut be = vec![0x90u8];
+        be.extend_from_slice(&value.to_be_bytes());
+        let mut le = vec![0x90u8];
+        le.extend_from_slice(&value.to_le_bytes());
+        ETestPair(BigEndian(value), be.clone()).run_encode_test();
+        DTestPair(BigEndian(value), be).run_decode_test();
+        ETestPair(LittleEndian(value), le.clone()).run_encode_test();
+        DTestPair(LittleEndian(value), le).run_decode_test();
+
+        let short: Result<BigEndian<U256>, _> = rlp::decode(&[0x82, 0x01, 0x02]);
+        assert_eq!(short, Err(rlp::DecoderError::RlpIsTooShort));
+        let value = U256::max_value() - 1;
+        let decoded: BigEndian<U256> = rlp::decode(&rlp::encode(&BigEndian(value))).unwrap();
+        assert_eq!(decoded, BigEndian(value));
+    }
+}
+
+mod construct_tests {
+    crate::construct_primitive_hash! {
+        /// Compressed public key.
+        pub struct H264(33);
+    }
+
+    crate::construct_primitive_hash! {
+        /// Uncompressed public key.
+        pub struct H520(65);
+    }
+
+    crate::construct_primitive_uint! {
+        /// 192-bit unsigned integer.
+        pub struct U192(3);
+    }
+
+    crate::construct_primitive_uint! {
+        /// 640-bit unsigned integer.
+        pub struct U640(10);
+    }
+
+    #[test]
+    fn test_construct_primitive() {
+        use core::convert::TryFrom;
+
+        use crate::{Error, TruncatingFrom};
+
+        let key = H264::repeat_byte(0x02);
+        assert_eq!(H264::try_from(&[0x02u8; 33][..]), Ok(key));
+        assert_eq!(
+            H264::try_from(&[0x02u8; 32][..]),
+            Err(Error::InvalidLength {
+                expected: 33,
+                actual: 32
+            })
+        );
+
+        let value = U192::from(0x0102);
+        assert_eq!(U192::from_be_bytes(value.to_be_bytes()), value);
+        assert_eq!(value.to_le_bytes()[..2], [0x02, 0x01]);
+        assert!(U192::try_from_big_endian(&[0u8; 25]).is_err());
+        assert_eq!(U192::try_from_i128(-1), Err(Error::Negative));
+        assert_eq!(U192::truncating_from(-1i128), U192::max_value());
+        assert_eq!((U192::one() << 128).try_to_u128(), Err(Error::Overflow));
+
+        #[cfg(feature = "serde")]
+        {
+            assert_eq!(serde_json::to_string(&value).unwrap(), r#""0x102""#);
+            assert_eq!(
+                serde_json::from_str::<H264>(&serde_json::to_string(&key).unwrap()).unwrap(),
+                key
+            );
+        }
+
+        #[cfg(feature = "codec")]
+        {
+            use impl_codec::codec::{Decode, Encode};
+
+            assert_eq!(key.encode(), [0x02u8; 33].to_vec());
+            assert_eq!(H264::decode(&mut &key.encode()[..]), Some(key));
+            assert_eq!(H264::decode(&mut &[0x02u8; 32][..]), None);
+            assert_eq!(U192::decode(&mut &value.encode()[..]), Some(value));
+
+            let mut bytes = [0x04u8; 65];
+            bytes[64] = 0x01;
+            let long_key = H520(bytes);
+            let encoded = long_key.encode();
+            assert_eq!(encoded, bytes.to_vec());
+            assert_eq!(H520::decode(&mut &encoded[..]), Some(long_key));
+            assert_eq!(H520::decode(&mut &encoded[1..]), None);
+
+            let wide = U640::max_value() - 1;
+            let encoded = wide.encode();
+            assert_eq!(encoded.len(), 80);
+            assert_eq!(U640::decode(&mut &encoded[..]), Some(wide));
+            assert_eq!(U640::decode(&mut &encoded[1..]), None);
+        }
+
+        #[cfg(feature = "scale-codec")]
+        {
+            use impl_codec::scale_codec::{Decode, Encode};
+
+            assert_eq!(key.encode(), [0x02u8; 33].to_vec());
+            assert_eq!(U192::decode(&mut &value.encode()[..]), Ok(value));
+        }
+
+        #[cfg(feature = "rlp")]
+        {
+            use impl_rlp::rlp;
+
+            assert_eq!(rlp::encode(&value), [0x82, 0x01, 0x02].to_vec());
+            assert_eq!(rlp::decode::<H264>(&rlp::encode(&key)), Ok(key));
+        }
+    }
+}
+
+#[test]
+fn test_fixed_arrays_roundtrip() {
+    use crate::U256;
+    let raw = U256::from(helper::from_hex_str("0x7094875209347850239487502394881").as_slice());
+    let array: [u8; 32] = raw.into();
+    let new_raw: U256 = array.into();
+    assert_eq!(raw, new_raw);
+}
+
+#[test]
+#[allow(clippy::cognitive_complexity)]
+fn test_u256_multi_full_mul() {
+    use core::u64::MAX;
+
+    use crate::{U256, U512};
+
+    assert_eq!(
+        U512([0, 0, 0, 0, 0, 0, 0, 0]),
+        U256([0, 0, 0, 0]).full_mul(U256([0, 0, 0, 0]))
+    );
+    assert_eq!(
+        U512([1, 0, 0, 0, 0, 0, 0, 0]),
+        U256([1, 0, 0, 0]).full_mul(U256([1, 0, 0, 0]))
+    );
+    assert_eq!(
+        U512([25, 0, 0, 0, 0, 0, 0, 0]),
+        U256([5, 0, 0, 0]).full_mul(U256([5, 0, 0, 0]))
+    );
+    assert_eq!(
+        U512([0, 0, 25, 0, 0, 0, 0, 0]),
+        U256([0, 5, 0, 0]).full_mul(U256([0, 5, 0, 0]))
+    );
+    assert_eq!(
+        U512([0, 0, 0, 16, 0, 0, 0, 0]),
+        U256([0, 0, 0, 4]).full_mul(U256([4, 0, 0, 0]))
+    );
+    assert_eq!(
+        U512([0, 0, 0, 10, 0, 0, 0, 0]),
         U256([0, 0, 0, 5]).full_mul(U256([2, 0, 0, 0]))
     );
     assert_eq!(
-        U512([0, 0, 0, 10, 0, 0, 0, 0]),
-        U256([0, 0, 2, 0]).full_mul(U256([0, 5, 0, 0]))
+        U512([0, 0, 0, 10, 0, 0, 0, 0]),
+        U256([0, 0, 2, 0]).full_mul(U256([0, 5, 0, 0]))
+    );
+    assert_eq!(
+        U512([0, 0, 0, 9, 0, 0, 0, 0]),
+        U256([0, 3, 0, 0]).full_mul(U256([0, 0, 3, 0]))
+    );
+    assert_eq!(
+        U512([0, 0, 0, 0, 48, 0, 0, 0]),
+        U256([0, 0, 8, 0]).full_mul(U256([0, 0, 6, 0]))
+    );
+    assert_eq!(
+        U512([0, 27, 0, 0, 0, 0, 0, 0]),
+        U256([9, 0, 0, 0]).full_mul(U256([0, 3, 0, 0]))
+    );
+    assert_eq!(
+        U512([1, MAX - 1, 0, 0, 0, 0, 0, 0]),
+        U256([MAX, 0, 0, 0]).full_mul(U256([MAX, 0, 0, 0]))
+    );
+    assert_eq!(
+        U512([0, 1, MAX - 1, 0, 0, 0, 0, 0]),
+        U256([0, MAX, 0, 0]).full_mul(U256([MAX, 0, 0, 0]))
+    );
+    assert_eq!(
+        U512([1, MAX, MAX - 1, 0, 0, 0, 0, 0]),
+        U256([MAX, MAX, 0, 0]).full_mul(U256([MAX, 0, 0, 0]))
+    );
+    assert_eq!(
+        U512([1, MAX, MAX - 1, 0, 0, 0, 0, 0]),
+        U256([MAX, 0, 0, 0]).full_mul(U256([MAX, MAX, 0, 0]))
+    );
+    assert_eq!(
+        U512([1, 0, MAX - 1, MAX, 0, 0, 0, 0]),
+        U256([MAX, MAX, 0, 0]).full_mul(U256([MAX, MAX, 0, 0]))
+    );
+    assert_eq!(
+        U512([1, MAX, MAX, MAX - 1, 0, 0, 0, 0]),
+        U256([MAX, 0, 0, 0]).full_mul(U256([MAX, MAX, MAX, 0]))
+    );
+    assert_eq!(
+        U512([1, MAX, MAX, MAX - 1, 0, 0, 0, 0]),
+        U256([MAX, MAX, MAX, 0]).full_mul(U256([MAX, 0, 0, 0]))
+    );
+    assert_eq!(
+        U512([1, MAX, MAX, MAX, MAX - 1, 0, 0, 0]),
+        U256([MAX, 0, 0, 0]).full_mul(U256([MAX, MAX, MAX, MAX]))
+    );
+    assert_eq!(
+        U512([1, MAX, MAX, MAX, MAX - 1, 0, 0, 0]),
+        U256([MAX, MAX, MAX, MAX]).full_mul(U256([MAX, 0, 0, 0]))
+    );
+    assert_eq!(
+        U512([1, 0, MAX, MAX - 1, MAX, 0, 0, 0]),
+        U256([MAX, MAX, MAX, 0]).full_mul(U256([MAX, MAX, 0, 0]))
+    );
+    assert_eq!(
+        U512([1, 0, MAX, MAX - 1, MAX, 0, 0, 0]),
+        U256([MAX, MAX, 0, 0]).full_mul(U256([MAX, MAX, MAX, 0]))
+    );
+    assert_eq!(
+        U512([1, 0, MAX, MAX, MAX - 1, MAX, 0, 0]),
+        U256([MAX, MAX, MAX, MAX]).full_mul(U256([MAX, MAX, 0, 0]))
+    );
+    assert_eq!(
+        U512([1, 0, MAX, MAX, MAX - 1, MAX, 0, 0]),
+        U256([MAX, MAX, 0, 0]).full_mul(U256([MAX, MAX, MAX, MAX]))
+    );
+    assert_eq!(
+        U512([1, 0, 0, MAX - 1, MAX, MAX, 0, 0]),
+        U256([MAX, MAX, MAX, 0]).full_mul(U256([MAX, MAX, MAX, 0]))
+    );
+    assert_eq!(
+        U512([1, 0, 0, MAX, MAX - 1, MAX, MAX, 0]),
+        U256([MAX, MAX, MAX, 0]).full_mul(U256([MAX, MAX, MAX, MAX]))
+    );
+    assert_eq!(
+        U512([1, 0, 0, MAX, MAX - 1, MAX, MAX, 0]),
+        U256([MAX, MAX, MAX, MAX]).full_mul(U256([MAX, MAX, MAX, 0]))
+    );
+    assert_eq!(
+        U512([1, 0, 0, 0, MAX - 1, MAX, MAX, MAX]),
+        U256([MAX, MAX, MAX, MAX]).full_mul(U256([MAX, MAX, MAX, MAX]))
+    );
+    assert_eq!(
+        U512([0, 0, 0, 0, 0, 0, 1, MAX - 1]),
+        U256([0, 0, 0, MAX]).full_mul(U256([0, 0, 0, MAX]))
+    );
+    assert_eq!(
+        U512([0, 0, 0, MAX, 0, 0, 0, 0]),
+        U256([1, 0, 0, 0]).full_mul(U256([0, 0, 0, MAX]))
+    );
+    assert_eq!(
+        U512([5, 10, 15, 20, 0, 0, 0, 0]),
+        U256([1, 2, 3, 4]).full_mul(U256([5, 0, 0, 0]))
+    );
+    assert_eq!(
+        U512([0, 6, 12, 18, 24, 0, 0, 0]),
+        U256([1, 2, 3, 4]).full_mul(U256([0, 6, 0, 0]))
+    );
+    assert_eq!(
+        U512([0, 0, 7, 14, 21, 28, 0, 0]),
+        U256([1, 2, 3, 4]).full_mul(U256([0, 0, 7, 0]))
+    );
+    assert_eq!(
+        U512([0, 0, 0, 8, 16, 24, 32, 0]),
+        U256([1, 2, 3, 4]).full_mul(U256([0, 0, 0, 8]))
+    );
+    assert_eq!(
+        U512([5, 16, 34, 60, 61, 52, 32, 0]),
+        U256([1, 2, 3, 4]).full_mul(U256([5, 6, 7, 8]))
+    );
+}
+
+#[test]
+fn test_u512_full_mul() {
+    use core::u64::MAX;
+
+    use crate::{U1024, U512};
+
+    let mut expected = [0; 16];
+    expected[0] = 1;
+    expected[8..].copy_from_slice(&[MAX - 1, MAX, MAX, MAX, MAX, MAX, MAX, MAX]);
+    assert_eq!(
+        U1024(expected),
+        U512::max_value().full_mul(U512::max_value())
+    );
+    assert_eq!(
+        U512([5, 6, 7, 8, 0, 0, 0, 0]).full_mul(U512([1, 2, 3, 4, 0, 0, 0, 0])),
+        U1024::from(U512([5, 16, 34, 60, 61, 52, 32, 0]))
+    );
+}
+
+#[test]
+fn test_error_display() {
+    #[cfg(not(feature = "std"))]
+    use alloc::format;
+
+    use uint::FromDecStrErr;
+
+    use crate::Error;
+
+    assert_eq!(
+        format!("{}", Error::Overflow),
+        "value overflows the target type"
+    );
+    assert_eq!(
+        format!(
+            "{}",
+            Error::InvalidLength {
+                expected: 32,
+                actual: 20
+            }
+        ),
+        "invalid length: expected 32, got 20"
+    );
+    assert_eq!(
+        format!("{}", Error::InvalidHexCharacter(3)),
+        "invalid hex character at position 3"
+    );
+    assert_eq!(format!("{}", Error::DivisionByZero), "division by zero");
+    assert_eq!(
+        Error::from(FromDecStrErr::InvalidCharacter),
+        Error::InvalidDecimalDigit
+    );
+    assert_eq!(Error::from(FromDecStrErr::InvalidLength), Error::Overflow);
+
+    #[cfg(feature = "std")]
+    {
+        fn parse(input: &str) -> Result<crate::U256, Box<dyn std::error::Error>> {
+            Ok(crate::U256::from_dec_str(input).map_err(Error::from)?)
+        }
+        assert_eq!(parse("12").unwrap(), crate::U256::from(12));
+        assert_eq!(
+            parse("1x").unwrap_err().to_string(),
+            "invalid decimal digit"
+        );
+    }
+}
+
+#[test]
+fn test_try_from_slice() {
+    use core::convert::TryFrom;
+
+    use crate::{Error, H160, H256, H512, U128, U256, U512};
+
+    let mut bytes = [0u8; 65];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = i as u8 + 1;
+    }
+    assert_eq!(
+        H160::try_from(&bytes[..20]),
+        Ok(H160::from_slice(&bytes[..20]))
+    );
+    assert_eq!(
+        H256::try_from(&bytes[..32]),
+        Ok(H256::from_slice(&bytes[..32]))
+    );
+    assert_eq!(
+        H512::try_from(&bytes[..64]),
+        Ok(H512::from_slice(&bytes[..64]))
+    );
+    assert_eq!(
+        H160::try_from(&bytes[..19]),
+        Err(Error::InvalidLength {
+            expected: 20,
+            actual: 19
+        })
+    );
+    assert_eq!(
+        H256::try_from(&bytes[..]),
+        Err(Error::InvalidLength {
+            expected: 32,
+            actual: 65
+        })
+    );
+    assert_eq!(
+        H512::try_from(&[][..]),
+        Err(Error::InvalidLength {
+            expected: 64,
+            actual: 0
+        })
+    );
+
+    assert_eq!(
+        U128::try_from_big_endian(&bytes[..16]),
+        Ok(U128::from_big_endian(&bytes[..16]))
+    );
+    assert_eq!(
+        U256::try_from_little_endian(&bytes[..32]),
+        Ok(U256::from_little_endian(&bytes[..32]))
+    );
+    assert_eq!(
+        U256::try_from_big_endian(&[0, 0, 1][..]),
+        Err(Error::InvalidLength {
+            expected: 32,
+            actual: 3
+        })
+    );
+    assert_eq!(
+        U512::try_from_big_endian(&bytes[..]),
+        Err(Error::InvalidLength {
+            expected: 64,
+            actual: 65
+        })
+    );
+    assert_eq!(
+        U512::try_from_little_endian(&bytes[..64]),
+        Ok(U512::from_little_endian(&bytes[..64]))
+    );
+}
+
+#[test]
+fn test_endian_bytes() {
+    use crate::{BigEndian, LittleEndian, U128, U256, U512};
+
+    let value = U256::from(0x0102_0304u64);
+    let be = value.to_be_bytes();
+    let le = value.to_le_bytes();
+    assert_eq!(be[28..], [1, 2, 3, 4]);
+    assert_eq!(le[..4], [4, 3, 2, 1]);
+    assert_eq!(be, <[u8; 32]>::from(value));
+    assert_eq!(U256::from_be_bytes(be), value);
+    assert_eq!(U256::from_le_bytes(le), value);
+
+    let value = U128::max_value() - 1;
+    assert_eq!(U128::from_le_bytes(value.to_le_bytes()), value);
+    assert_eq!(U128::from_be_bytes(value.to_be_bytes()), value);
+    let value = U512::max_value() - 1;
+    assert_eq!(value.to_le_bytes()[0], 0xfe);
+    assert_eq!(value.to_be_bytes()[63], 0xfe);
+    assert_eq!(U512::from_le_bytes(value.to_le_bytes()), value);
+
+    let value = U256::from(42);
+    assert_eq!(U256::from(BigEndian::from(value)), value);
+    assert_eq!(LittleEndian::from(value).into_inner(), value);
+}
+
+#[test]
+#[allow(clippy::cognitive_complexity)]
+fn test_conversion_matrix() {
+    use core::convert::TryFrom;
+
+    use crate::{
+        BigEndian, Error, LittleEndian, SaturatingFrom, TruncatingFrom, H160, H256, H512, U1024,
+        U128, U256, U512,
+    };
+
+    // uints
+    let wide = (U256::one() << 128) + 5;
+    assert_eq!(U128::try_from(wide), Err(Error::Overflow));
+    assert_eq!(U128::truncating_from(wide), U128::from(5));
+    assert_eq!(U128::saturating_from(wide), U128::max_value());
+    assert_eq!(U128::saturating_from(U256::from(5)), U128::from(5));
+    assert_eq!(U256::truncating_from(U512::max_value()), U256::max_value());
+    assert_eq!(
+        U256::saturating_from(U512::from(U256::max_value()) + 1),
+        U256::max_value()
+    );
+    assert_eq!(
+        U512::truncating_from(U1024::from(U512::max_value()) + 3),
+        U512::from(2)
+    );
+
+    // hashes
+    let low = H256::repeat_byte(0x11);
+    let wide = H512::from(low);
+    assert_eq!(wide[..32], [0u8; 32]);
+    assert_eq!(wide[32..], low[..]);
+    assert_eq!(H256::try_from(wide), Ok(low));
+    let mut high = wide;
+    high.0[0] = 1;
+    assert_eq!(H256::try_from(high), Err(Error::Overflow));
+    assert_eq!(H256::truncating_from(high), low);
+    assert_eq!(H256::saturating_from(high), H256::repeat_byte(0xff));
+    assert_eq!(H256::saturating_from(wide), low);
+    let mut hash = H256::repeat_byte(0x22);
+    assert_eq!(H160::truncating_from(hash), H160::from(hash));
+    assert_eq!(H160::saturating_from(hash), H160::repeat_byte(0xff));
+    hash.0[..12].copy_from_slice(&[0u8; 12]);
+    assert_eq!(H160::saturating_from(hash), H160::repeat_byte(0x22));
+
+    // hashes and uints of the same width
+    let value = U256::from(0x0102);
+    let be = H256::from(BigEndian(value));
+    let le = H256::from(LittleEndian(value));
+    assert_eq!(be[30..], [1, 2]);
+    assert_eq!(le[..2], [2, 1]);
+    assert_eq!(BigEndian::<U256>::from(be).into_inner(), value);
+    assert_eq!(LittleEndian::<U256>::from(le).into_inner(), value);
+    let value = U512::max_value() - 1;
+    assert_eq!(H512::from(BigEndian(value))[63], 0xfe);
+    assert_eq!(H512::from(LittleEndian(value))[0], 0xfe);
+    assert_eq!(
+        LittleEndian::<U512>::from(H512::from(LittleEndian(value))),
+        LittleEndian(value)
+    );
+
+    // addresses
+    let mut address = H160::zero();
+    address.0[18] = 1;
+    address.0[19] = 2;
+    assert_eq!(U256::from(address), U256::from(0x0102));
+    assert_eq!(
+        U256::from(H160::repeat_byte(0xff)),
+        (U256::one() << 160) - 1
     );
+    assert_eq!(H160::try_from(U256::from(0x0102)), Ok(address));
     assert_eq!(
-        U512([0, 0, 0, 9, 0, 0, 0, 0]),
-        U256([0, 3, 0, 0]).full_mul(U256([0, 0, 3, 0]))
+        H160::try_from((U256::one() << 160) - 1),
+        Ok(H160::repeat_byte(0xff))
     );
+    assert_eq!(H160::try_from(U256::one() << 160), Err(Error::Overflow));
     assert_eq!(
-        U512([0, 0, 0, 0, 48, 0, 0, 0]),
-        U256([0, 0, 8, 0]).full_mul(U256([0, 0, 6, 0]))
+        H160::truncating_from((U256::one() << 160) + 0x0102),
+        address
     );
     assert_eq!(
-        U512([0, 27, 0, 0, 0, 0, 0, 0]),
-        U256([9, 0, 0, 0]).full_mul(U256([0, 3, 0, 0]))
+        H160::saturating_from(U256::max_value()),
+        H160::repeat_byte(0xff)
     );
+
+    // native integers
+    assert_eq!(U128::try_from_i128(5), Ok(U128::from(5)));
+    assert_eq!(U128::try_from_i128(-5), Err(Error::Negative));
+    assert_eq!(U128::saturating_from(-5i128), U128::zero());
+    assert_eq!(U128::truncating_from(-1i128), U128::max_value());
+    assert_eq!(U256::truncating_from(-2i128), U256::max_value() - 1);
     assert_eq!(
-        U512([1, MAX - 1, 0, 0, 0, 0, 0, 0]),
-        U256([MAX, 0, 0, 0]).full_mul(U256([MAX, 0, 0, 0]))
+        U256::truncating_from(i128::max_value()),
+        U256::from(i128::max_value() as u128)
     );
+    assert_eq!(U128::max_value().try_to_u128(), Ok(u128::max_value()));
+    assert_eq!((U256::one() << 128).try_to_u128(), Err(Error::Overflow));
+    assert_eq!(U128::from(5).try_to_i128(), Ok(5));
+    assert_eq!(U128::max_value().try_to_i128(), Err(Error::Overflow));
+    assert_eq!(u128::truncating_from(U256::max_value()), u128::max_value());
     assert_eq!(
-        U512([0, 1, MAX - 1, 0, 0, 0, 0, 0]),
-        U256([0, MAX, 0, 0]).full_mul(U256([MAX, 0, 0, 0]))
+        u128::saturating_from((U512::one() << 128) + 1),
+        u128::max_value()
     );
+    assert_eq!(u128::saturating_from(U512::from(7)), 7);
+    assert_eq!(i128::truncating_from(U128::max_value()), -1);
+    assert_eq!(i128::saturating_from(U128::max_value()), i128::max_value());
+    assert_eq!(i128::saturating_from(U256::from(7)), 7);
+}
+
+#[test]
+#[allow(clippy::cognitive_complexity)]
+fn test_mixed_width() {
+    use crate::{Error, U128, U256, U512};
+
+    let fee = U128::from(5);
+    let balance = U256::from(100);
+    let product = U512::from(1000);
+
+    assert_eq!(fee + balance, U256::from(105));
+    assert_eq!(balance + fee, U256::from(105));
+    assert_eq!(fee - U256::from(3), U256::from(2));
+    assert_eq!(balance - fee, U256::from(95));
+    assert_eq!(fee * balance, U256::from(500));
+    assert_eq!(balance * fee, U256::from(500));
+    assert_eq!(fee / U256::from(2), U256::from(2));
+    assert_eq!(balance / fee, U256::from(20));
+    assert_eq!(fee % U256::from(3), U256::from(2));
+    assert_eq!(balance % U128::from(7), U256::from(2));
+    assert_eq!(fee * product, U512::from(5000));
+    assert_eq!(product * fee, U512::from(5000));
+    assert_eq!(balance * product, U512::from(100_000));
+    assert_eq!(product * balance, U512::from(100_000));
+    assert_eq!(balance + product, U512::from(1100));
+    assert_eq!(product - balance, U512::from(900));
     assert_eq!(
-        U512([1, MAX, MAX - 1, 0, 0, 0, 0, 0]),
-        U256([MAX, MAX, 0, 0]).full_mul(U256([MAX, 0, 0, 0]))
+        U128::max_value() * U256::from(U128::max_value()),
+        U128::max_value().full_mul(U128::max_value())
     );
+
+    assert!(fee == U256::from(5));
+    assert!(U256::from(5) == fee);
+    assert!(fee != balance);
+    assert!(fee < balance);
+    assert!(balance > fee);
+    assert!(product > balance);
+    assert!(balance < product);
+    assert!(U512::from(U256::max_value()) + 1 > U256::max_value());
+    assert!(U128::max_value() <= U512::from(U128::max_value()));
+
+    assert_eq!(fee.narrowing_add(balance), Ok(U128::from(105)));
+    assert_eq!(U128::max_value().narrowing_add(fee), Err(Error::Overflow));
     assert_eq!(
-        U512([1, MAX, MAX - 1, 0, 0, 0, 0, 0]),
-        U256([MAX, 0, 0, 0]).full_mul(U256([MAX, MAX, 0, 0]))
+        U256::max_value().narrowing_add(U512::max_value()),
+        Err(Error::Overflow)
     );
+    assert_eq!(balance.narrowing_sub(product), Err(Error::Underflow));
+    assert_eq!(balance.narrowing_sub(fee), Ok(U256::from(95)));
+    assert_eq!(balance.narrowing_mul(product), Ok(U256::from(100_000)));
     assert_eq!(
-        U512([1, 0, MAX - 1, MAX, 0, 0, 0, 0]),
-        U256([MAX, MAX, 0, 0]).full_mul(U256([MAX, MAX, 0, 0]))
+        U256::max_value().narrowing_mul(U256::max_value()),
+        Err(Error::Overflow)
     );
     assert_eq!(
-        U512([1, MAX, MAX, MAX - 1, 0, 0, 0, 0]),
-        U256([MAX, 0, 0, 0]).full_mul(U256([MAX, MAX, MAX, 0]))
+        U256::max_value().narrowing_mul(U512::max_value()),
+        Err(Error::Overflow)
     );
+    assert_eq!(fee.narrowing_div(product), Ok(U128::zero()));
+    assert_eq!(balance.narrowing_rem(product), Ok(balance));
+    assert_eq!(fee.narrowing_mul(3u64), Ok(U128::from(15)));
+}
+
+#[test]
+#[allow(clippy::cognitive_complexity)]
+fn test_wrapping_saturating() {
+    #[cfg(not(feature = "std"))]
+    use alloc::format;
+
+    use crate::{Saturating, Wrapping, U128, U256};
+
+    let max = Wrapping(U256::max_value());
+    let one = Wrapping(U256::one());
+    assert_eq!(max + one, Wrapping(U256::zero()));
+    assert_eq!(Wrapping(U256::zero()) - one, max);
+    assert_eq!(max * Wrapping(U256::from(2)), max - one);
+    assert_eq!(-one, max);
+    assert_eq!(-Wrapping(U256::zero()), Wrapping(U256::zero()));
     assert_eq!(
-        U512([1, MAX, MAX, MAX - 1, 0, 0, 0, 0]),
-        U256([MAX, MAX, MAX, 0]).full_mul(U256([MAX, 0, 0, 0]))
+        max / Wrapping(U256::from(2)),
+        Wrapping(U256::max_value() >> 1)
     );
     assert_eq!(
-        U512([1, MAX, MAX, MAX, MAX - 1, 0, 0, 0]),
-        U256([MAX, 0, 0, 0]).full_mul(U256([MAX, MAX, MAX, MAX]))
+        Wrapping(U256::from(7)) % Wrapping(U256::from(4)),
+        Wrapping(U256::from(3))
     );
+    assert_eq!(one << 256, one);
+    assert_eq!(one << 257, Wrapping(U256::from(2)));
+    assert_eq!(Wrapping(U256::from(4)) >> 258, one);
+    assert_eq!(!Wrapping(U256::zero()), max);
+    assert_eq!(max & one, one);
+    assert_eq!(one | Wrapping(U256::from(2)), Wrapping(U256::from(3)));
+    assert_eq!(one ^ max, Wrapping(U256::max_value() - 1));
+
+    let mut acc = Wrapping(U128::max_value());
+    acc += Wrapping(U128::from(2));
+    assert_eq!(acc, Wrapping(U128::one()));
+    acc -= Wrapping(U128::from(2));
+    assert_eq!(acc, Wrapping(U128::max_value()));
+    acc *= Wrapping(U128::max_value());
+    assert_eq!(acc, Wrapping(U128::one()));
+    acc <<= 129;
+    assert_eq!(acc, Wrapping(U128::from(2)));
+    acc >>= 1;
+    assert_eq!(U128::from(acc), U128::one());
+
+    let max = Saturating(U256::max_value());
+    let one = Saturating(U256::one());
+    assert_eq!(max + one, max);
+    assert_eq!(Saturating(U256::zero()) - one, Saturating(U256::zero()));
+    assert_eq!(max * Saturating(U256::from(2)), max);
     assert_eq!(
-        U512([1, MAX, MAX, MAX, MAX - 1, 0, 0, 0]),
-        U256([MAX, MAX, MAX, MAX]).full_mul(U256([MAX, 0, 0, 0]))
+        Saturating(U256::from(6)) * Saturating(U256::from(7)),
+        Saturating(U256::from(42))
     );
+    assert_eq!(max / Saturating(U256::max_value()), one);
+    assert_eq!(!max, Saturating(U256::zero()));
+
+    let mut acc = Saturating(U128::from(5));
+    acc -= Saturating(U128::from(10));
+    assert_eq!(acc, Saturating(U128::zero()));
+    acc += Saturating(U128::max_value());
+    acc += Saturating(U128::one());
+    assert_eq!(acc, Saturating::from(U128::max_value()));
+
+    assert_eq!(format!("{}", Wrapping(U256::from(42))), "42");
+    assert_eq!(format!("{:x}", Saturating(U256::from(255))), "ff");
+}
+
+#[test]
+#[allow(clippy::cognitive_complexity)]
+fn test_checked_math() {
+    #[cfg(not(feature = "std"))]
+    use alloc::vec;
+
+    use crate::{CheckedMath, Error, U128, U256};
+
+    fn transfer(balance: U256, amount: U256, fee: U256) -> Result<U256, Error> {
+        balance.try_sub(amount.try_add(fee)?)
+    }
+
     assert_eq!(
-        U512([1, 0, MAX, MAX - 1, MAX, 0, 0, 0]),
-        U256([MAX, MAX, MAX, 0]).full_mul(U256([MAX, MAX, 0, 0]))
+        transfer(U256::from(100), U256::from(60), U256::from(1)),
+        Ok(U256::from(39))
     );
     assert_eq!(
-        U512([1, 0, MAX, MAX - 1, MAX, 0, 0, 0]),
-        U256([MAX, MAX, 0, 0]).full_mul(U256([MAX, MAX, MAX, 0]))
+        transfer(U256::from(100), U256::from(100), U256::from(1)),
+        Err(Error::Underflow)
     );
     assert_eq!(
-        U512([1, 0, MAX, MAX, MAX - 1, MAX, 0, 0]),
-        U256([MAX, MAX, MAX, MAX]).full_mul(U256([MAX, MAX, 0, 0]))
+        transfer(U256::from(100), U256::max_value(), U256::from(1)),
+        Err(Error::Overflow)
     );
+
+    assert_eq!(U128::max_value().try_add(U128::one()), Err(Error::Overflow));
+    assert_eq!(U128::zero().try_sub(U128::one()), Err(Error::Underflow));
     assert_eq!(
-        U512([1, 0, MAX, MAX, MAX - 1, MAX, 0, 0]),
-        U256([MAX, MAX, 0, 0]).full_mul(U256([MAX, MAX, MAX, MAX]))
+        U128::max_value().try_mul(U128::from(2)),
+        Err(Error::Overflow)
     );
+    assert_eq!(U128::from(6).try_mul(U128::from(7)), Ok(U128::from(42)));
+    assert_eq!(U128::from(42).try_div(U128::from(5)), Ok(U128::from(8)));
     assert_eq!(
-        U512([1, 0, 0, MAX - 1, MAX, MAX, 0, 0]),
-        U256([MAX, MAX, MAX, 0]).full_mul(U256([MAX, MAX, MAX, 0]))
+        U128::one().try_div(U128::zero()),
+        Err(Error::DivisionByZero)
     );
+    assert_eq!(U256::from(2).try_pow(255), Ok(U256::one() << 255));
+    assert_eq!(U256::from(2).try_pow(256), Err(Error::Overflow));
+    assert_eq!(U256::zero().try_neg(), Ok(U256::zero()));
+    assert_eq!(U256::one().try_neg(), Err(Error::Underflow));
+
+    assert_eq!(250u8.try_add(5), Ok(255));
+    assert_eq!(250u8.try_add(6), Err(Error::Overflow));
+    assert_eq!(1u64.try_sub(2), Err(Error::Underflow));
+    assert_eq!(1usize.try_div(0), Err(Error::DivisionByZero));
+    assert_eq!(3u32.try_pow(20), Ok(3_486_784_401));
+    assert_eq!(3u32.try_pow(21), Err(Error::Overflow));
+    assert_eq!(0u128.try_neg(), Ok(0));
+    assert_eq!(7u16.try_neg(), Err(Error::Underflow));
+
+    let balances = [U256::from(1), U256::from(2), U256::from(3)];
+    assert_eq!(U256::try_sum(balances.iter().cloned()), Ok(U256::from(6)));
     assert_eq!(
-        U512([1, 0, 0, MAX, MAX - 1, MAX, MAX, 0]),
-        U256([MAX, MAX, MAX, 0]).full_mul(U256([MAX, MAX, MAX, MAX]))
+        U256::try_product(balances.iter().cloned()),
+        Ok(U256::from(6))
     );
+    assert_eq!(U256::try_sum(None), Ok(U256::zero()));
+    assert_eq!(U256::try_product(None), Ok(U256::one()));
     assert_eq!(
-        U512([1, 0, 0, MAX, MAX - 1, MAX, MAX, 0]),
-        U256([MAX, MAX, MAX, MAX]).full_mul(U256([MAX, MAX, MAX, 0]))
+        U256::try_sum([U256::max_value(), U256::one()].iter().cloned()),
+        Err(Error::Overflow)
     );
     assert_eq!(
-        U512([1, 0, 0, 0, MAX - 1, MAX, MAX, MAX]),
-        U256([MAX, MAX, MAX, MAX]).full_mul(U256([MAX, MAX, MAX, MAX]))
+        U256::try_product([U256::one() << 128, U256::one() << 128].iter().cloned()),
+        Err(Error::Overflow)
     );
+    assert_eq!(u8::try_sum(vec![100, 100, 55]), Ok(255));
+    assert_eq!(u8::try_product(vec![16, 16]), Err(Error::Overflow));
+}
+
+#[test]
+fn test_u128_full_mul() {
+    use core::u64::MAX;
+
+    use crate::{U128, U256};
+
     assert_eq!(
-        U512([0, 0, 0, 0, 0, 0, 1, MAX - 1]),
-        U256([0, 0, 0, MAX]).full_mul(U256([0, 0, 0, MAX]))
+        U256([1, 0, MAX - 1, MAX]),
+        U128::max_value().full_mul(U128::max_value())
     );
+    assert_eq!(U256([5, 16, 12, 0]), U128([1, 2]).full_mul(U128([5, 6])));
+}
+
+#[test]
+fn test_wide_split_and_div() {
+    use crate::{Error, U1024, U128, U256, U512};
+
+    let wide = U256([1, 2, 3, 4]);
+    assert_eq!(wide.into_halves(), (U128([3, 4]), U128([1, 2])));
+    assert_eq!(U256::from_halves(U128([3, 4]), U128([1, 2])), wide);
+
+    let wide = U512([1, 2, 3, 4, 5, 6, 7, 8]);
+    assert_eq!(wide.into_halves(), (U256([5, 6, 7, 8]), U256([1, 2, 3, 4])));
+    let (hi, lo) = wide.into_halves();
+    assert_eq!(U512::from_halves(hi, lo), wide);
+
+    let wide = U512::max_value().full_mul(U512::max_value());
+    let (hi, lo) = wide.into_halves();
+    assert_eq!(hi, U512::max_value() - 1);
+    assert_eq!(lo, U512::one());
+    assert_eq!(U1024::from_halves(hi, lo), wide);
+
+    let a = U256::max_value();
+    let b = U256::from(u64::max_value());
+    assert_eq!(a.full_mul(b).div_mod_narrow(a), Ok((b, U256::zero())));
+    let (q, r) = a.full_mul(b).div_mod_narrow(b + 1).unwrap();
+    assert_eq!(q.full_mul(b + 1) + U512::from(r), a.full_mul(b));
+    assert!(r < b + 1);
     assert_eq!(
-        U512([0, 0, 0, MAX, 0, 0, 0, 0]),
-        U256([1, 0, 0, 0]).full_mul(U256([0, 0, 0, MAX]))
+        U256::from(10)
+            .full_mul(U256::from(10))
+            .div_mod_narrow(U256::from(7)),
+        Ok((U256::from(14), U256::from(2)))
     );
+    assert_eq!(a.full_mul(a).div_mod_narrow(b), Err(Error::Overflow));
     assert_eq!(
-        U512([5, 10, 15, 20, 0, 0, 0, 0]),
-        U256([1, 2, 3, 4]).full_mul(U256([5, 0, 0, 0]))
+        U128::max_value()
+            .full_mul(U128::max_value())
+            .div_mod_narrow(U128::max_value()),
+        Ok((U128::max_value(), U128::zero()))
+    );
+}
+
+#[test]
+fn test_u1024_conversions() {
+    use core::convert::TryFrom;
+
+    use crate::{Error, U1024, U128, U256, U512};
+
+    let value = U1024::from(U128::max_value());
+    assert_eq!(U128::try_from(value), Ok(U128::max_value()));
+    assert_eq!(U128::try_from(value + 1), Err(Error::Overflow));
+
+    let value = U1024::from(U256::max_value());
+    assert_eq!(U256::try_from(value), Ok(U256::max_value()));
+    assert_eq!(U256::try_from(value + 1), Err(Error::Overflow));
+
+    let value = U1024::from(&U512::max_value());
+    assert_eq!(U512::try_from(&value), Ok(U512::max_value()));
+    assert_eq!(U512::try_from(value + 1), Err(Error::Overflow));
+}
+
+#[test]
+fn test_mul_div() {
+    #[cfg(not(feature = "std"))]
+    use alloc::vec;
+
+    use crate::{Error, Rounding, U128, U256, U512};
+
+    let tests = vec![
+        // (a, b, c, floor, ceil, half up, half even)
+        (7, 3, 2, 10, 11, 11, 10),
+        (9, 3, 2, 13, 14, 14, 14),
+        (10, 3, 4, 7, 8, 8, 8),
+        (10, 1, 4, 2, 3, 3, 2),
+        (10, 1, 3, 3, 4, 3, 3),
+        (12, 1, 3, 4, 4, 4, 4),
+        (0, 5, 3, 0, 0, 0, 0),
+    ];
+    for (a, b, c, floor, ceil, half_up, half_even) in tests {
+        let (a, b, c) = (U256::from(a), U256::from(b), U256::from(c));
+        assert_eq!(a.mul_div_floor(b, c), Ok(U256::from(floor)));
+        assert_eq!(a.mul_div_ceil(b, c), Ok(U256::from(ceil)));
+        assert_eq!(a.mul_div_round(b, c), Ok(U256::from(half_up)));
+        assert_eq!(a.mul_div_round_half_even(b, c), Ok(U256::from(half_even)));
+        assert_eq!(a.mul_div(b, c, Rounding::Floor), Ok(U256::from(floor)));
+    }
+
+    // the product overflows, the quotient does not
+    let max = U256::max_value();
+    assert_eq!(max.mul_div_floor(max, max), Ok(max));
+    assert_eq!(max.mul_div_ceil(max - 1, max), Ok(max - 1));
+    assert_eq!(
+        max.mul_div_floor(U256::from(2), U256::from(3)),
+        Ok(max / 3 * 2)
     );
+    assert_eq!(max.mul_div_floor(max, max - 1), Err(Error::Overflow));
+    assert_eq!(max.mul_div_ceil(U256::one(), U256::one()), Ok(max));
+
+    let max = U128::max_value();
+    assert_eq!(max.mul_div_floor(max, max), Ok(max));
+    assert_eq!(max.mul_div_round(U128::from(2), U128::from(2)), Ok(max));
     assert_eq!(
-        U512([0, 6, 12, 18, 24, 0, 0, 0]),
-        U256([1, 2, 3, 4]).full_mul(U256([0, 6, 0, 0]))
+        max.mul_div_floor(U128::from(2), U128::one()),
+        Err(Error::Overflow)
     );
+
+    let max = U512::max_value();
+    assert_eq!(max.mul_div_floor(max, max), Ok(max));
     assert_eq!(
-        U512([0, 0, 7, 14, 21, 28, 0, 0]),
-        U256([1, 2, 3, 4]).full_mul(U256([0, 0, 7, 0]))
+        max.mul_div_ceil(U512::from(3), U512::from(4)),
+        Ok(max / 4 * 3 + 3)
     );
+    assert_eq!(max.mul_div_floor(max, U512::from(2)), Err(Error::Overflow));
+}
+
+#[test]
+#[allow(clippy::cognitive_complexity)]
+fn test_int_arithmetic() {
+    use crate::{I256, U256};
+
+    let minus_one = I256::from(-1);
+    assert_eq!(minus_one.to_bits(), U256::max_value());
+    assert_eq!(I256::from(7) + I256::from(-10), I256::from(-3));
+    assert_eq!(I256::from(-7) - 10, I256::from(-17));
+    assert_eq!(I256::from(-7) * -6, I256::from(42));
+    assert_eq!(I256::from(-7) / 2, I256::from(-3));
+    assert_eq!(I256::from(-7) % 2, I256::from(-1));
+    assert_eq!(I256::from(7) % -2, I256::from(1));
+    assert_eq!(-I256::from(5), I256::from(-5));
+    assert_eq!(I256::from(-3).pow(3), I256::from(-27));
+    assert_eq!(I256::from(-8) >> 1, I256::from(-4));
+    assert_eq!(minus_one >> 255, minus_one);
+    assert_eq!(I256::from(-1) << 4, I256::from(-16));
+    assert_eq!(I256::from(-5).abs(), I256::from(5));
+    assert_eq!(I256::from(-5).signum(), minus_one);
+    assert_eq!(I256::min_value().unsigned_abs(), U256::one() << 255);
+
+    assert_eq!(I256::max_value().checked_add(1.into()), None);
+    assert_eq!(I256::min_value().checked_sub(1.into()), None);
+    assert_eq!(I256::min_value().checked_neg(), None);
+    assert_eq!(I256::min_value().checked_div(minus_one), None);
+    assert_eq!(I256::from(1).checked_div(I256::zero()), None);
+    assert_eq!(I256::min_value().checked_mul(minus_one), None);
     assert_eq!(
-        U512([0, 0, 0, 8, 16, 24, 32, 0]),
-        U256([1, 2, 3, 4]).full_mul(U256([0, 0, 0, 8]))
+        I256::min_value().checked_mul(I256::one()),
+        Some(I256::min_value())
     );
+    assert_eq!(I256::from(2).checked_pow(255), None);
+    assert_eq!(I256::from(-2).checked_pow(255), Some(I256::min_value()));
     assert_eq!(
-        U512([5, 16, 34, 60, 61, 52, 32, 0]),
-        U256([1, 2, 3, 4]).full_mul(U256([5, 6, 7, 8]))
+        I256::max_value().overflowing_add(1.into()),
+        (I256::min_value(), true)
+    );
+    assert_eq!(
+        I256::min_value().saturating_sub(1.into()),
+        I256::min_value()
+    );
+    assert_eq!(
+        I256::max_value().saturating_mul(minus_one * 2),
+        I256::min_value()
+    );
+
+    assert!(I256::min_value() < minus_one);
+    assert!(minus_one < I256::zero());
+    assert!(I256::zero() < I256::max_value());
+    assert!(I256::from(-2) < minus_one);
+}
+
+#[test]
+fn test_int_conversions() {
+    use core::convert::TryFrom;
+
+    use crate::{Error, I128, I256, I512, U128, U256};
+
+    assert_eq!(
+        I256::try_from(U256::from(5)).map(I256::to_bits),
+        Ok(U256::from(5))
+    );
+    assert_eq!(I256::try_from(U256::max_value()), Err(Error::Overflow));
+    assert_eq!(U256::try_from(I256::from(5)), Ok(U256::from(5)));
+    assert_eq!(U256::try_from(I256::from(-5)), Err(Error::Negative));
+
+    assert_eq!(I512::from(I256::from(-5)), I512::from(-5));
+    assert_eq!(
+        I256::from(U128::max_value()).to_bits(),
+        U256::from(u128::max_value())
+    );
+    assert_eq!(I128::try_from(I512::from(-5)), Ok(I128::from(-5)));
+    assert_eq!(
+        I128::try_from(I256::from(U128::max_value())),
+        Err(Error::Overflow)
+    );
+    assert_eq!(
+        I128::try_from(I256::from(i128::min_value())),
+        Ok(I128::min_value())
+    );
+    assert_eq!(
+        I128::try_from(I256::from(i128::min_value()) - 1),
+        Err(Error::Overflow)
+    );
+
+    assert_eq!(i64::try_from(I256::from(-5)), Ok(-5));
+    assert_eq!(
+        i64::try_from(I256::from(u64::max_value())),
+        Err(Error::Overflow)
+    );
+    assert_eq!(i128::try_from(I128::min_value()), Ok(i128::min_value()));
+    assert_eq!(
+        i128::try_from(I256::from(i128::max_value()) + 1),
+        Err(Error::Overflow)
+    );
+}
+
+#[test]
+fn test_int_display_from_str() {
+    #[cfg(not(feature = "std"))]
+    use alloc::{format, vec};
+
+    use uint::FromDecStrErr;
+
+    use crate::I128;
+
+    let tests = vec![
+        (I128::zero(), "0"),
+        (I128::from(-1), "-1"),
+        (I128::from(1_000), "1000"),
+        (I128::max_value(), "170141183460469231731687303715884105727"),
+        (
+            I128::min_value(),
+            "-170141183460469231731687303715884105728",
+        ),
+    ];
+    for (number, expected) in tests {
+        assert_eq!(format!("{}", number), expected);
+        assert_eq!(expected.parse::<I128>(), Ok(number));
+    }
+    assert_eq!("+12".parse::<I128>(), Ok(I128::from(12)));
+    assert_eq!(
+        format!("{:x}", I128::from(-1)),
+        "ffffffffffffffffffffffffffffffff"
+    );
+    assert_eq!(
+        "170141183460469231731687303715884105728".parse::<I128>(),
+        Err(FromDecStrErr::InvalidLength)
+    );
+    assert_eq!("-".parse::<I128>(), Err(FromDecStrErr::InvalidCharacter));
+    assert_eq!("1-2".parse::<I128>(), Err(FromDecStrErr::InvalidCharacter));
+}
+
+#[test]
+fn test_fixed_arithmetic() {
+    use crate::{FixedU256, Rounding, D18, D6, U256};
+
+    type Fixed = FixedU256<D6>;
+    let fixed = |s: &str| s.parse::<Fixed>().unwrap();
+
+    assert_eq!(Fixed::one().into_inner(), U256::from(1_000_000));
+    assert_eq!(Fixed::checked_from_integer(3), Some(fixed("3")));
+    assert_eq!(Fixed::checked_from_rational(1, 3), Some(fixed("0.333333")));
+    assert_eq!(Fixed::checked_from_rational(1, 0), None);
+    assert_eq!(fixed("1.5") + fixed("2.25"), fixed("3.75"));
+    assert_eq!(fixed("1.5") - fixed("0.25"), fixed("1.25"));
+    assert_eq!(fixed("1.5") * fixed("2.5"), fixed("3.75"));
+    assert_eq!(fixed("1") / fixed("8"), fixed("0.125"));
+    assert_eq!(fixed("12.75").trunc(), U256::from(12));
+    assert_eq!(fixed("12.75").fract(), fixed("0.75"));
+
+    let third = fixed("2").checked_div_round(fixed("3"), Rounding::Floor);
+    assert_eq!(third, Some(fixed("0.666666")));
+    let third = fixed("2").checked_div_round(fixed("3"), Rounding::Ceil);
+    assert_eq!(third, Some(fixed("0.666667")));
+    let half = fixed("0.000001").checked_mul_round(fixed("0.5"), Rounding::HalfUp);
+    assert_eq!(half, Some(fixed("0.000001")));
+    let half = fixed("0.000001").checked_mul_round(fixed("0.5"), Rounding::HalfEven);
+    assert_eq!(half, Some(fixed("0")));
+    let half = fixed("0.000003").checked_mul_round(fixed("0.5"), Rounding::HalfEven);
+    assert_eq!(half, Some(fixed("0.000002")));
+
+    // the intermediate product does not fit in 256 bits, the result does
+    let max = FixedU256::<D18>::max_value();
+    assert_eq!(max.checked_mul(FixedU256::one()), Some(max));
+    assert_eq!(max.checked_div(FixedU256::one()), Some(max));
+    assert_eq!(
+        max.checked_mul(FixedU256::checked_from_integer(2).unwrap()),
+        None
+    );
+    assert_eq!(max.saturating_add(FixedU256::one()), max);
+    assert_eq!(max.saturating_mul(max), max);
+    assert_eq!(Fixed::zero().saturating_sub(Fixed::one()), Fixed::zero());
+    assert_eq!(Fixed::one().checked_div(Fixed::zero()), None);
+}
+
+#[test]
+fn test_fixed_display_from_str() {
+    #[cfg(not(feature = "std"))]
+    use alloc::{format, vec};
+
+    use uint::FromDecStrErr;
+
+    use crate::{FixedU256, D18, U256};
+
+    type Fixed = FixedU256<D18>;
+
+    let tests = vec![
+        (U256::zero(), "0"),
+        (U256::from(1), "0.000000000000000001"),
+        (U256::exp10(18), "1"),
+        (U256::from(1_234_500_000_000_000_000u64), "1.2345"),
+        (
+            U256::max_value(),
+            "115792089237316195423570985008687907853269984665640564039457.584007913129639935",
+        ),
+    ];
+    for (inner, expected) in tests {
+        let number = Fixed::from_inner(inner);
+        assert_eq!(format!("{}", number), expected);
+        assert_eq!(expected.parse::<Fixed>(), Ok(number));
+    }
+    assert_eq!(
+        "1.2345000000000000000000".parse::<Fixed>(),
+        "1.2345".parse::<Fixed>()
+    );
+    assert_eq!(
+        "1.0000000000000000001".parse::<Fixed>(),
+        Err(FromDecStrErr::InvalidLength)
+    );
+    assert_eq!(
+        "115792089237316195423570985008687907853269984665640564039458".parse::<Fixed>(),
+        Err(FromDecStrErr::InvalidLength)
+    );
+    assert_eq!(".5".parse::<Fixed>(), Err(FromDecStrErr::InvalidCharacter));
+    assert_eq!("1.".parse::<Fixed>(), Err(FromDecStrErr::InvalidCharacter));
+    assert_eq!("1.a".parse::<Fixed>(), Err(FromDecStrErr::InvalidCharacter));
+    assert_eq!(
+        "1.2.3".parse::<Fixed>(),
+        Err(FromDecStrErr::InvalidCharacter)
+    );
+
+    let number = "1.25".parse::<Fixed>().unwrap();
+    assert_eq!(format!("{:>8}", number), "    1.25");
+    assert_eq!(format!("{:*<8}", number), "1.25****");
+    assert_eq!(format!("{:^8}", number), "  1.25  ");
+    assert_eq!(format!("{:08}", number), "00001.25");
+    assert_eq!(format!("{:+}", number), "+1.25");
+    assert_eq!(format!("{:3}", number), "1.25");
+    assert_eq!(format!("{:?}", Fixed::zero()), "0");
+}
+
+#[test]
+fn test_fixed_max_decimals() {
+    #[cfg(not(feature = "std"))]
+    use alloc::format;
+
+    use crate::{Decimals, FixedU256, U256};
+
+    struct D77;
+    impl Decimals for D77 {
+        const DECIMALS: u32 = 77;
+    }
+
+    let number = FixedU256::<D77>::from_inner(U256::max_value());
+    assert_eq!(FixedU256::<D77>::accuracy(), U256::exp10(77));
+    assert_eq!(
+        format!("{}", number),
+        "1.15792089237316195423570985008687907853269984665640564039457584007913129639935"
+    );
+    assert_eq!(format!("{}", number).parse(), Ok(number));
+}
+
+#[test]
+#[should_panic(expected = "Decimals::DECIMALS must be at most 77")]
+fn test_fixed_too_many_decimals() {
+    use crate::{Decimals, FixedU256};
+
+    struct D78;
+    impl Decimals for D78 {
+        const DECIMALS: u32 = 78;
+    }
+
+    FixedU256::<D78>::one();
+}
+
+#[test]
+fn test_roots() {
+    use crate::{U1024, U128, U256, U512};
+
+    assert_eq!(U256::zero().isqrt(), U256::zero());
+    assert_eq!(U256::one().isqrt(), U256::one());
+    assert_eq!(U256::from(15).isqrt(), U256::from(3));
+    assert_eq!(U256::from(16).isqrt(), U256::from(4));
+    assert_eq!(U128::max_value().isqrt(), U128::from(u64::max_value()));
+    assert_eq!(U256::max_value().isqrt(), U256::from(U128::max_value()));
+    assert_eq!(U1024::max_value().isqrt(), U1024::from(U512::max_value()));
+
+    // beyond the precision of `f64`
+    let root = U256::from(1u64 << 53) + 1;
+    assert_eq!((root * root).isqrt(), root);
+    assert_eq!((root * root - 1).isqrt(), root - 1);
+
+    // geometric mean of two reserves
+    let (reserve_a, reserve_b) = (U256::exp10(30), U256::exp10(24) * 4);
+    let product: U256 = reserve_a * reserve_b;
+    assert_eq!(product.isqrt(), U256::exp10(27) * 2);
+
+    assert_eq!(U256::from(26).integer_nth_root(1), U256::from(26));
+    assert_eq!(U256::from(26).integer_nth_root(3), U256::from(2));
+    assert_eq!(U256::from(27).integer_nth_root(3), U256::from(3));
+    assert_eq!(U256::from(1_000_000).integer_nth_root(6), U256::from(10));
+    assert_eq!(
+        U256::max_value().integer_nth_root(2),
+        U256::from(U128::max_value())
+    );
+    assert_eq!(U256::max_value().integer_nth_root(255), U256::from(2));
+    assert_eq!(U256::max_value().integer_nth_root(256), U256::one());
+    assert_eq!(U256::max_value().integer_nth_root(1000), U256::one());
+    assert_eq!(U256::zero().integer_nth_root(7), U256::zero());
+    let root = U512::from(u64::max_value()) - 2;
+    assert_eq!(root.pow(5.into()).integer_nth_root(5), root);
+    assert_eq!((root.pow(5.into()) - 1).integer_nth_root(5), root - 1);
+}
+
+#[test]
+#[should_panic(expected = "zeroth root")]
+fn test_zeroth_root() {
+    crate::U256::one().integer_nth_root(0);
+}
+
+#[test]
+#[allow(clippy::cognitive_complexity)]
+fn test_logs() {
+    use crate::{U128, U256, U512};
+
+    assert_eq!(U256::one().log2_floor(), 0);
+    assert_eq!(U256::one().log2_ceil(), 0);
+    assert_eq!(U256::from(2).log2_ceil(), 1);
+    assert_eq!(U256::from(5).log2_floor(), 2);
+    assert_eq!(U256::from(5).log2_ceil(), 3);
+    assert_eq!((U256::one() << 200).log2_floor(), 200);
+    assert_eq!((U256::one() << 200).log2_ceil(), 200);
+    assert_eq!(((U256::one() << 200) + 1).log2_ceil(), 201);
+    assert_eq!(U128::max_value().log2_floor(), 127);
+    assert_eq!(U512::max_value().log2_ceil(), 512);
+
+    assert_eq!(U256::one().log10(), 0);
+    assert_eq!(U256::from(9).log10(), 0);
+    assert_eq!(U256::from(10).log10(), 1);
+    assert_eq!(U256::exp10(77).log10(), 77);
+    assert_eq!((U256::exp10(77) - 1).log10(), 76);
+    assert_eq!(U256::max_value().log10(), 77);
+    assert_eq!(U512::max_value().log10(), 154);
+    assert_eq!(U128::max_value().log10(), 38);
+
+    assert_eq!(U256::from(80).log_base(3.into()), 3);
+    assert_eq!(U256::from(81).log_base(3.into()), 4);
+    assert_eq!(U256::max_value().log_base(2.into()), 255);
+    assert_eq!(U256::max_value().log_base(U256::max_value()), 1);
+    assert_eq!((U256::max_value() - 1).log_base(U256::max_value()), 0);
+    assert_eq!(U512::from(3).pow(300.into()).log_base(3.into()), 300);
+    assert_eq!((U512::from(3).pow(300.into()) - 1).log_base(3.into()), 299);
+}
+
+#[test]
+#[should_panic(expected = "logarithm of zero")]
+fn test_log_of_zero() {
+    crate::U256::zero().log10();
+}
+
+#[test]
+#[allow(clippy::cognitive_complexity)]
+fn test_mod_arith() {
+    use crate::{U256, U512};
+
+    let max = U256::max_value();
+    // EVM: a zero modulus yields zero
+    assert_eq!(U256::from(5).add_mod(3.into(), 0.into()), U256::zero());
+    assert_eq!(U256::from(5).sub_mod(3.into(), 0.into()), U256::zero());
+    assert_eq!(U256::from(5).mul_mod(3.into(), 0.into()), U256::zero());
+    assert_eq!(U256::from(5).pow_mod(3.into(), 0.into()), U256::zero());
+    assert_eq!(U256::from(5).inv_mod(0.into()), None);
+
+    assert_eq!(U256::from(5).add_mod(3.into(), 7.into()), U256::one());
+    assert_eq!(max.add_mod(max, 7.into()), (max % 7) * 2 % 7);
+    // the sum wraps around 2^256 but the result must not
+    assert_eq!(max.add_mod(2.into(), max), U256::from(2));
+    assert_eq!(max.add_mod(max - 1, max - 2), U256::from(3));
+
+    assert_eq!(U256::from(3).sub_mod(5.into(), 7.into()), U256::from(5));
+    assert_eq!(U256::zero().sub_mod(max, max - 1), max - 2);
+    assert_eq!(max.sub_mod(max, 7.into()), U256::zero());
+
+    assert_eq!(max.mul_mod(max, max), U256::zero());
+    assert_eq!(max.mul_mod(max, max - 1), U256::one());
+    assert_eq!(max.mul_mod(2.into(), 12.into()), U256::from(6));
+    assert_eq!(
+        U512::max_value().mul_mod(U512::max_value(), 10.into()),
+        U512::from(5)
+    );
+
+    assert_eq!(
+        U256::from(2).pow_mod(10.into(), 1000.into()),
+        U256::from(24)
     );
+    assert_eq!(U256::zero().pow_mod(0.into(), 7.into()), U256::one());
+    assert_eq!(U256::from(5).pow_mod(0.into(), 1.into()), U256::zero());
+    // Fermat's little theorem for the secp256k1 field prime
+    let p = U256::from_dec_str(
+        "115792089237316195423570985008687907853269984665640564039457584007908834671663",
+    )
+    .unwrap();
+    let a = U256::from_dec_str("123456789012345678901234567890").unwrap();
+    assert_eq!(a.pow_mod(p - 1, p), U256::one());
+
+    let inverse = a.inv_mod(p).unwrap();
+    assert_eq!(inverse, a.pow_mod(p - 2, p));
+    assert_eq!(a.mul_mod(inverse, p), U256::one());
+    assert_eq!(U256::from(3).inv_mod(7.into()), Some(U256::from(5)));
+    assert_eq!(U256::from(10).inv_mod(7.into()), Some(U256::from(5)));
+    assert_eq!(U256::from(4).inv_mod(8.into()), None);
+    assert_eq!(U256::zero().inv_mod(7.into()), None);
+    assert_eq!(U256::from(3).inv_mod(1.into()), Some(U256::zero()));
+    assert_eq!(max.inv_mod(max - 1), Some(U256::one()));
+    let inverse = U512::from(p).inv_mod(U512::max_value()).unwrap();
+    assert_eq!(
+        U512::from(p).mul_mod(inverse, U512::max_value()),
+        U512::one()
+    );
+}
+
+#[test]
+fn test_montgomery() {
+    use crate::{MontgomeryContext, U256, U512};
+
+    assert_eq!(MontgomeryContext::<U256>::new(U256::zero()), None);
+    assert_eq!(MontgomeryContext::<U256>::new(U256::one()), None);
+    assert_eq!(MontgomeryContext::<U256>::new(U256::from(10)), None);
+
+    let p = U256::from_dec_str(
+        "115792089237316195423570985008687907853269984665640564039457584007908834671663",
+    )
+    .unwrap();
+    let n = U256::from_dec_str(
+        "115792089237316195423570985008687907852837564279074904382605163141518161494337",
+    )
+    .unwrap();
+    let values = [
+        U256::zero(),
+        U256::one(),
+        U256::from(2),
+        U256::from(u64::max_value()),
+        U256::max_value() / 3,
+        U256::max_value(),
+    ];
+    for &modulus in &[p, n, U256::from(7), U256::max_value()] {
+        let ctx = MontgomeryContext::<U256>::new(modulus).unwrap();
+        assert_eq!(ctx.modulus(), modulus);
+        assert_eq!(ctx.from_montgomery(ctx.one()), U256::one());
+        for &a in &values {
+            let a_mont = ctx.to_montgomery(a);
+            assert_eq!(ctx.from_montgomery(a_mont), a % modulus);
+            assert_eq!(
+                ctx.from_montgomery(ctx.square(a_mont)),
+                a.mul_mod(a, modulus)
+            );
+            for &b in &values {
+                let product = ctx.mul(a_mont, ctx.to_montgomery(b));
+                assert_eq!(ctx.from_montgomery(product), a.mul_mod(b, modulus));
+            }
+            let power = ctx.pow(a_mont, modulus - 2);
+            assert_eq!(ctx.from_montgomery(power), a.pow_mod(modulus - 2, modulus));
+        }
+    }
+
+    let modulus = U512::max_value() - 2;
+    let ctx = MontgomeryContext::<U512>::new(modulus).unwrap();
+    let (a, b) = (U512::max_value() / 3, U512::from(p) * U512::from(n));
+    let product = ctx.mul(ctx.to_montgomery(a), ctx.to_montgomery(b));
+    assert_eq!(ctx.from_montgomery(product), a.mul_mod(b, modulus));
+    let power = ctx.pow(ctx.to_montgomery(a), p.into());
+    assert_eq!(ctx.from_montgomery(power), a.pow_mod(p.into(), modulus));
 }
 
 mod helper {
@@ -0,0 +1,74 @@
+// Copyright 2019 Chainpool
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Conversions with the types of parity's upstream `primitive-types` 0.7 and
+//! `ethereum-types` 0.9.
+//!
+//! `ethereum-types` re-exports the `H160`, `H256`, `H512`, `U128`, `U256` and
+//! `U512` of upstream `primitive-types`, so either feature provides the same
+//! conversions. `ethereum_types::Address` is `H160`.
+//!
+//! Both sides are `#[repr(C)]` wrappers around the same array, so `From` only
+//! moves the array and slices are reinterpreted in place.
+
+use core::mem::{align_of, size_of};
+use core::slice;
+
+#[cfg(not(feature = "upstream-primitive-types"))]
+use ethereum_types as upstream;
+#[cfg(feature = "upstream-primitive-types")]
+use upstream_primitive_types as upstream;
+
+use crate::{H160, H256, H512, U128, U256, U512};
+
+macro_rules! impl_upstream_conversions {
+    ($name:ident) => {
+        const _: () = {
+            // Slices are only reinterpreted if the layouts are the same.
+            let _: [(); size_of::<$name>()] = [(); size_of::<upstream::$name>()];
+            let _: [(); align_of::<$name>()] = [(); align_of::<upstream::$name>()];
+        };
+
+        impl From<upstream::$name> for $name {
+            #[inline]
+            fn from(value: upstream::$name) -> Self {
+                $name(value.0)
+            }
+        }
+
+        impl From<$name> for upstream::$name {
+            #[inline]
+            fn from(value: $name) -> Self {
+                upstream::$name(value.0)
+            }
+        }
+
+        impl $name {
+            /// Views a slice of the upstream type as a slice of this type, without copying.
+            pub fn from_upstream_slice(values: &[upstream::$name]) -> &[$name] {
+                // Both types are `#[repr(C)]` with the same single field.
+                unsafe { slice::from_raw_parts(values.as_ptr() as *const $name, values.len()) }
+            }
+
+            /// Views a slice of this type as a slice of the upstream type, without copying.
+            pub fn as_upstream_slice(values: &[$name]) -> &[upstream::$name] {
+                // Both types are `#[repr(C)]` with the same single field.
+                unsafe {
+                    slice::from_raw_parts(values.as_ptr() as *const upstream::$name, values.len())
+                }
+            }
+        }
+    };
+}
+
+impl_upstream_conversions!(H160);
+impl_upstream_conversions!(H256);
+impl_upstream_conversions!(H512);
+impl_upstream_conversions!(U128);
+impl_upstream_conversions!(U256);
+impl_upstream_conversions!(U512);
@@ -0,0 +1,391 @@
+// Copyright 2019 Chainpool
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Uint newtypes whose arithmetic operators wrap around or saturate instead of
+//! panicking on overflow.
+//!
+//! They mirror `core::num::Wrapping`, which can not be used because the operator
+//! traits can not be implemented for it outside of `core`. Serialization is
+//! transparent, the same as for the inner value.
+
+use core::fmt;
+use core::ops::{
+    Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Div, DivAssign,
+    Mul, MulAssign, Neg, Not, Rem, RemAssign, Shl, ShlAssign, Shr, ShrAssign, Sub, SubAssign,
+};
+
+use crate::{U1024, U128, U256, U512};
+
+/// Uint with wrapping arithmetic: results are taken modulo `2^BITS`.
+///
+/// Division and remainder by zero still panic.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Wrapping<T>(pub T);
+
+/// Uint with saturating arithmetic: results are clamped to `[0, MAX]`.
+///
+/// Division and remainder by zero still panic.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Saturating<T>(pub T);
+
+impl<T: fmt::Display> fmt::Display for Wrapping<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Saturating<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: fmt::LowerHex> fmt::LowerHex for Wrapping<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: fmt::LowerHex> fmt::LowerHex for Saturating<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+macro_rules! impl_op {
+    ($wrapper:ident, $name:ident, $trait:ident, $fn:ident, $assign_trait:ident, $assign_fn:ident, |$a:ident, $b:ident| $body:expr) => {
+        impl $trait for $wrapper<$name> {
+            type Output = Self;
+
+            #[inline]
+            fn $fn(self, other: Self) -> Self {
+                let ($a, $b) = (self.0, other.0);
+                $wrapper($body)
+            }
+        }
+
+        impl $assign_trait for $wrapper<$name> {
+            #[inline]
+            fn $assign_fn(&mut self, other: Self) {
+                *self = $trait::$fn(*self, other);
+            }
+        }
+    };
+}
+
+macro_rules! impl_common_ops {
+    ($wrapper:ident, $name:ident) => {
+        impl_op!($wrapper, $name, Div, div, DivAssign, div_assign, |a, b| a
+            / b);
+        impl_op!($wrapper, $name, Rem, rem, RemAssign, rem_assign, |a, b| a
+            % b);
+        impl_op!(
+            $wrapper,
+            $name,
+            BitAnd,
+            bitand,
+            BitAndAssign,
+            bitand_assign,
+            |a, b| a & b
+        );
+        impl_op!(
+            $wrapper,
+            $name,
+            BitOr,
+            bitor,
+            BitOrAssign,
+            bitor_assign,
+            |a, b| a | b
+        );
+        impl_op!(
+            $wrapper,
+            $name,
+            BitXor,
+            bitxor,
+            BitXorAssign,
+            bitxor_assign,
+            |a, b| a ^ b
+        );
+
+        impl Not for $wrapper<$name> {
+            type Output = Self;
+
+            #[inline]
+            fn not(self) -> Self {
+                $wrapper(!self.0)
+            }
+        }
+
+        impl From<$name> for $wrapper<$name> {
+            fn from(value: $name) -> Self {
+                $wrapper(value)
+            }
+        }
+
+        impl From<$wrapper<$name>> for $name {
+            fn from(value: $wrapper<$name>) -> Self {
+                value.0
+            }
+        }
+    };
+}
+
+macro_rules! impl_wrapping {
+    ($name:ident, $n_words:tt) => {
+        impl_common_ops!(Wrapping, $name);
+        impl_op!(Wrapping, $name, Add, add, AddAssign, add_assign, |a, b| a
+            .overflowing_add(b)
+            .0);
+        impl_op!(Wrapping, $name, Sub, sub, SubAssign, sub_assign, |a, b| a
+            .overflowing_sub(b)
+            .0);
+        impl_op!(Wrapping, $name, Mul, mul, MulAssign, mul_assign, |a, b| a
+            .overflowing_mul(b)
+            .0);
+
+        impl Neg for Wrapping<$name> {
+            type Output = Self;
+
+            #[inline]
+            fn neg(self) -> Self {
+                Wrapping($name::zero().overflowing_sub(self.0).0)
+            }
+        }
+
+        impl Shl<usize> for Wrapping<$name> {
+            type Output = Self;
+
+            /// Shifts by `shift` modulo the number of bits, like `core::num::Wrapping`.
+            #[inline]
+            fn shl(self, shift: usize) -> Self {
+                Wrapping(self.0 << (shift % ($n_words * 64)))
+            }
+        }
+
+        impl Shr<usize> for Wrapping<$name> {
+            type Output = Self;
+
+            /// Shifts by `shift` modulo the number of bits, like `core::num::Wrapping`.
+            #[inline]
+            fn shr(self, shift: usize) -> Self {
+                Wrapping(self.0 >> (shift % ($n_words * 64)))
+            }
+        }
+
+        impl ShlAssign<usize> for Wrapping<$name> {
+            #[inline]
+            fn shl_assign(&mut self, shift: usize) {
+                *self = *self << shift;
+            }
+        }
+
+        impl ShrAssign<usize> for Wrapping<$name> {
+            #[inline]
+            fn shr_assign(&mut self, shift: usize) {
+                *self = *self >> shift;
+            }
+        }
+    };
+}
+
+macro_rules! impl_saturating {
+    ($name:ident) => {
+        impl_common_ops!(Saturating, $name);
+        impl_op!(
+            Saturating,
+            $name,
+            Add,
+            add,
+            AddAssign,
+            add_assign,
+            |a, b| a.saturating_add(b)
+        );
+        impl_op!(
+            Saturating,
+            $name,
+            Sub,
+            sub,
+            SubAssign,
+            sub_assign,
+            |a, b| a.saturating_sub(b)
+        );
+        impl_op!(
+            Saturating,
+            $name,
+            Mul,
+            mul,
+            MulAssign,
+            mul_assign,
+            |a, b| a.saturating_mul(b)
+        );
+    };
+}
+
+impl_wrapping!(U128, 2);
+impl_wrapping!(U256, 4);
+impl_wrapping!(U512, 8);
+impl_wrapping!(U1024, 16);
+
+impl_saturating!(U128);
+impl_saturating!(U256);
+impl_saturating!(U512);
+impl_saturating!(U1024);
+
+#[cfg(feature = "impl-serde")]
+mod serde_impls {
+    use impl_serde::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{Saturating, Wrapping};
+
+    macro_rules! impl_transparent_serde {
+        ($wrapper:ident) => {
+            impl<T: Serialize> Serialize for $wrapper<T> {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: Serializer,
+                {
+                    self.0.serialize(serializer)
+                }
+            }
+
+            impl<'de, T: Deserialize<'de>> Deserialize<'de> for $wrapper<T> {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    T::deserialize(deserializer).map($wrapper)
+                }
+            }
+        };
+    }
+
+    impl_transparent_serde!(Wrapping);
+    impl_transparent_serde!(Saturating);
+}
+
+#[cfg(feature = "codec")]
+mod codec_impls {
+    use impl_codec::codec::{Decode, Encode, Input};
+
+    use super::{Saturating, Wrapping};
+
+    macro_rules! impl_transparent_codec {
+        ($wrapper:ident) => {
+            impl<T: Encode> Encode for $wrapper<T> {
+                fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+                    self.0.using_encoded(f)
+                }
+            }
+
+            impl<T: Decode> Decode for $wrapper<T> {
+                fn decode<I: Input>(input: &mut I) -> Option<Self> {
+                    T::decode(input).map($wrapper)
+                }
+            }
+        };
+    }
+
+    impl_transparent_codec!(Wrapping);
+    impl_transparent_codec!(Saturating);
+}
+
+#[cfg(feature = "scale-codec")]
+mod scale_codec_impls {
+    use impl_codec::scale_codec::{Decode, Encode, EncodeLike, Error, Input};
+    use impl_codec::MaxEncodedLen;
+
+    use super::{Saturating, Wrapping};
+
+    macro_rules! impl_transparent_scale_codec {
+        ($wrapper:ident) => {
+            impl<T: Encode> Encode for $wrapper<T> {
+                fn size_hint(&self) -> usize {
+                    self.0.size_hint()
+                }
+
+                fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+                    self.0.using_encoded(f)
+                }
+            }
+
+            impl<T: Encode> EncodeLike for $wrapper<T> {}
+
+            impl<T: MaxEncodedLen> MaxEncodedLen for $wrapper<T> {
+                fn max_encoded_len() -> usize {
+                    T::max_encoded_len()
+                }
+            }
+
+            impl<T: Decode> Decode for $wrapper<T> {
+                fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+                    T::decode(input).map($wrapper)
+                }
+            }
+        };
+    }
+
+    impl_transparent_scale_codec!(Wrapping);
+    impl_transparent_scale_codec!(Saturating);
+}
+
+#[cfg(feature = "scale-info")]
+mod scale_info_impls {
+    use core::iter;
+
+    use scale_info_crate::build::Fields;
+    use scale_info_crate::{MetaType, Path, Type, TypeInfo, TypeParameter};
+
+    use super::{Saturating, Wrapping};
+
+    macro_rules! impl_transparent_type_info {
+        ($wrapper:ident) => {
+            impl<T: TypeInfo + 'static> TypeInfo for $wrapper<T> {
+                type Identity = Self;
+
+                fn type_info() -> Type {
+                    Type::builder()
+                        .path(Path::new(stringify!($wrapper), "primitive_types"))
+                        .type_params(iter::once(TypeParameter::new(
+                            "T",
+                            Some(MetaType::new::<T>()),
+                        )))
+                        .composite(Fields::unnamed().field(|f| f.ty::<T>().type_name("T")))
+                }
+            }
+        };
+    }
+
+    impl_transparent_type_info!(Wrapping);
+    impl_transparent_type_info!(Saturating);
+}
+
+#[cfg(feature = "impl-rlp")]
+mod rlp_impls {
+    use impl_rlp::rlp::{Decodable, DecoderError, Encodable, Rlp, RlpStream};
+
+    use super::{Saturating, Wrapping};
+
+    macro_rules! impl_transparent_rlp {
+        ($wrapper:ident) => {
+            impl<T: Encodable> Encodable for $wrapper<T> {
+                fn rlp_append(&self, s: &mut RlpStream) {
+                    self.0.rlp_append(s)
+                }
+            }
+
+            impl<T: Decodable> Decodable for $wrapper<T> {
+                fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+                    T::decode(rlp).map($wrapper)
+                }
+            }
+        };
+    }
+
+    impl_transparent_rlp!(Wrapping);
+    impl_transparent_rlp!(Saturating);
+}